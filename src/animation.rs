@@ -0,0 +1,131 @@
+use ggez::graphics::{Canvas, DrawParam, Image, Rect};
+
+use crate::player::Direction;
+
+// Planches de 3 images par ligne, une ligne par type de mouvement
+pub const SHEET_COLUMNS: u32 = 3;
+pub const SHEET_ROWS: u32 = 4;
+
+// Découpage d'une image du gestionnaire d'assets en une grille d'images
+pub struct SpriteSheet<'a> {
+    image: &'a Image,
+    columns: u32,
+    rows: u32,
+}
+
+impl<'a> SpriteSheet<'a> {
+    pub fn new(image: &'a Image, columns: u32, rows: u32) -> Self {
+        SpriteSheet { image, columns, rows }
+    }
+
+    // Dessine l'image demandée, centrée sur `param.dest` ; `scale` s'applique en plus
+    pub fn draw(&self, canvas: &mut Canvas, frame: AnimationFrame, param: DrawParam, scale: f32) {
+        let width = 1.0 / self.columns as f32;
+        let height = 1.0 / self.rows as f32;
+        let source = Rect::new(frame.column as f32 * width, frame.row as f32 * height, width, height);
+        let flip = if frame.flip_x { -1.0 } else { 1.0 };
+
+        canvas.draw(
+            self.image,
+            param
+                .src(source)
+                .offset([0.5, 0.5])
+                .scale([scale * flip, scale]),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Clip {
+    Idle,
+    Walk(Direction),
+    Attack,
+    Hurt,
+    Death,
+}
+
+struct ClipDefinition {
+    row: u32,
+    frames: u32,
+    frame_duration: f32,
+    looping: bool,
+}
+
+impl Clip {
+    fn definition(self) -> ClipDefinition {
+        match self {
+            Clip::Idle => ClipDefinition { row: 0, frames: 3, frame_duration: 0.3, looping: true },
+            Clip::Walk(_) => ClipDefinition { row: 1, frames: 3, frame_duration: 0.12, looping: true },
+            Clip::Attack => ClipDefinition { row: 2, frames: 3, frame_duration: 0.08, looping: false },
+            Clip::Hurt => ClipDefinition { row: 3, frames: 3, frame_duration: 0.1, looping: false },
+            Clip::Death => ClipDefinition { row: 3, frames: 3, frame_duration: 0.2, looping: false },
+        }
+    }
+
+    fn duration(self) -> f32 {
+        let definition = self.definition();
+        definition.frames as f32 * definition.frame_duration
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationFrame {
+    pub column: u32,
+    pub row: u32,
+    pub flip_x: bool,
+}
+
+// État de lecture d'une entité : le clip en cours et l'instant où il a commencé
+#[derive(Debug, Clone)]
+pub struct Animator {
+    clip: Clip,
+    started_at: f32,
+    flip_x: bool,
+}
+
+impl Animator {
+    pub fn new(clip: Clip, time: f32) -> Self {
+        Animator { clip, started_at: time, flip_x: false }
+    }
+
+    // Ne redémarre le clip que s'il change, pour ne pas figer la première image
+    pub fn play(&mut self, clip: Clip, time: f32) {
+        // On garde le dernier sens horizontal quand l'entité s'arrête ou frappe
+        match clip {
+            Clip::Walk(Direction::Left) => self.flip_x = true,
+            Clip::Walk(Direction::Right) => self.flip_x = false,
+            _ => {}
+        }
+
+        if clip != self.clip {
+            self.clip = clip;
+            self.started_at = time;
+        }
+    }
+
+    pub fn frame(&self, time: f32) -> AnimationFrame {
+        let definition = self.clip.definition();
+        let elapsed = (time - self.started_at).max(0.0);
+        let index = (elapsed / definition.frame_duration) as u32;
+        let column = if definition.looping {
+            index % definition.frames
+        } else {
+            index.min(definition.frames - 1)
+        };
+
+        AnimationFrame {
+            column,
+            row: definition.row,
+            flip_x: self.flip_x,
+        }
+    }
+
+    // Avancement de 0 à 1 d'un clip non bouclé
+    pub fn progress(&self, time: f32) -> f32 {
+        ((time - self.started_at) / self.clip.duration()).clamp(0.0, 1.0)
+    }
+
+    pub fn is_finished(&self, time: f32) -> bool {
+        !self.clip.definition().looping && self.progress(time) >= 1.0
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use ggez::Context;
+use ggez::graphics::Image;
+
+use crate::animation::{SpriteSheet, SHEET_COLUMNS, SHEET_ROWS};
+use crate::items::ItemType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageId {
+    PlayerSheet,
+    EnemySheet,
+    Dragon,
+    Coin,
+    Potion,
+    Weapon,
+    Artifact,
+    Tileset,
+}
+
+impl ImageId {
+    pub const ALL: [ImageId; 8] = [
+        ImageId::PlayerSheet,
+        ImageId::EnemySheet,
+        ImageId::Dragon,
+        ImageId::Coin,
+        ImageId::Potion,
+        ImageId::Weapon,
+        ImageId::Artifact,
+        ImageId::Tileset,
+    ];
+
+    pub fn path(self) -> &'static str {
+        match self {
+            ImageId::PlayerSheet => "/player_sheet.png",
+            ImageId::EnemySheet => "/enemy_sheet.png",
+            ImageId::Dragon => "/dragon.png",
+            ImageId::Coin => "/coin.png",
+            ImageId::Potion => "/potion.png",
+            ImageId::Weapon => "/weapon.png",
+            ImageId::Artifact => "/artifact.png",
+            ImageId::Tileset => "/tileset.png",
+        }
+    }
+}
+
+// Chaque image est décodée une seule fois ; une `Image` ggez est un simple handle partagé
+pub struct Assets {
+    images: HashMap<ImageId, Image>,
+}
+
+impl Assets {
+    pub fn load(ctx: &mut Context) -> Self {
+        let mut assets = Assets { images: HashMap::new() };
+        assets.reload(ctx);
+        assets
+    }
+
+    // Recharge toutes les images depuis le disque, sans arrêter le jeu
+    pub fn reload(&mut self, ctx: &mut Context) {
+        self.images.clear();
+        let mut missing = 0;
+
+        for id in ImageId::ALL {
+            match Image::from_path(ctx, id.path()) {
+                Ok(image) => {
+                    self.images.insert(id, image);
+                }
+                Err(e) => {
+                    println!(" Image manquante {} : {}", id.path(), e);
+                    missing += 1;
+                }
+            }
+        }
+
+        if missing > 0 {
+            println!(" {} image(s) manquante(s), formes géométriques utilisées à la place", missing);
+        }
+    }
+
+    pub fn image(&self, id: ImageId) -> Option<&Image> {
+        self.images.get(&id)
+    }
+
+    fn sheet(&self, id: ImageId) -> Option<SpriteSheet<'_>> {
+        self.image(id).map(|image| SpriteSheet::new(image, SHEET_COLUMNS, SHEET_ROWS))
+    }
+
+    pub fn player(&self) -> Option<SpriteSheet<'_>> {
+        self.sheet(ImageId::PlayerSheet)
+    }
+
+    pub fn enemy(&self) -> Option<SpriteSheet<'_>> {
+        self.sheet(ImageId::EnemySheet)
+    }
+
+    pub fn boss(&self) -> Option<&Image> {
+        self.image(ImageId::Dragon)
+    }
+
+    pub fn item(&self, item_type: ItemType) -> Option<&Image> {
+        match item_type {
+            ItemType::Coin => self.image(ImageId::Coin),
+            ItemType::Potion => self.image(ImageId::Potion),
+            ItemType::Weapon(_) => self.image(ImageId::Weapon),
+            ItemType::QuestArtifact => self.image(ImageId::Artifact),
+            ItemType::Key | ItemType::Bomb => None,
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use ggez::{Context, GameResult, graphics};
+use ggez::graphics::{Canvas, DrawParam, Color, Image, Mesh};
+use ggez::mint::Point2;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::Collider;
+use crate::enemy::EnemyType;
+use crate::map::Map;
+use crate::projectile::{Projectile, ProjectileKind, ProjectileOwner};
+
+pub const BOSS_RADIUS: f32 = 28.0;
+const BOSS_HEALTH: i32 = 400;
+const BOSS_REACH: f32 = 12.0;
+const INTRO_DURATION: f32 = 3.0;
+const SWEEP_RADIUS: f32 = 90.0;
+const CHARGE_SPEED: f32 = 260.0;
+const CHARGE_DURATION: f32 = 0.6;
+const FIREBALL_DAMAGE: i32 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BossPhase {
+    Intro,
+    Awakened,
+    Enraged,
+    Desperate,
+}
+
+impl BossPhase {
+    pub fn label(self) -> &'static str {
+        match self {
+            BossPhase::Intro => "Réveil",
+            BossPhase::Awakened => "Phase 1",
+            BossPhase::Enraged => "Phase 2 - Fureur",
+            BossPhase::Desperate => "Phase 3 - Désespoir",
+        }
+    }
+
+    fn speed(self) -> f32 {
+        match self {
+            BossPhase::Intro => 0.0,
+            BossPhase::Awakened => 45.0,
+            BossPhase::Enraged => 55.0,
+            BossPhase::Desperate => 65.0,
+        }
+    }
+
+    fn bite_cooldown(self) -> f32 {
+        match self {
+            BossPhase::Desperate => 1.0,
+            _ => 1.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum BossAction {
+    Hit { damage: i32, attack: &'static str },
+    Summon(EnemyType, u32),
+    Shoot(Projectile),
+    PhaseChanged(BossPhase),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Boss {
+    pub id: u32,
+    pub position: Point2<f32>,
+    pub health: i32,
+    pub max_health: i32,
+    pub phase: BossPhase,
+    pub is_alive: bool,
+    intro_timer: f32,
+    bite_timer: f32,
+    sweep_timer: f32,
+    charge_timer: f32,
+    charge_time_left: f32,
+    charge_direction: Point2<f32>,
+    fireball_timer: f32,
+}
+
+impl Boss {
+    pub fn new(id: u32, x: f32, y: f32) -> Self {
+        Boss {
+            id,
+            position: Point2 { x, y },
+            health: BOSS_HEALTH,
+            max_health: BOSS_HEALTH,
+            phase: BossPhase::Intro,
+            is_alive: true,
+            intro_timer: INTRO_DURATION,
+            bite_timer: 0.0,
+            sweep_timer: 4.0,
+            charge_timer: 3.0,
+            charge_time_left: 0.0,
+            charge_direction: Point2 { x: 0.0, y: 0.0 },
+            fireball_timer: 2.0,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, player: &Collider, map: &Map) -> Vec<BossAction> {
+        let mut actions = Vec::new();
+        if !self.is_alive {
+            return actions;
+        }
+
+        if self.phase == BossPhase::Intro {
+            self.intro_timer -= dt;
+            if self.intro_timer <= 0.0 {
+                self.phase = BossPhase::Awakened;
+                actions.push(BossAction::PhaseChanged(self.phase));
+            }
+            return actions;
+        }
+
+        self.update_phase(&mut actions);
+
+        self.bite_timer -= dt;
+        self.sweep_timer -= dt;
+        self.charge_timer -= dt;
+        self.fireball_timer -= dt;
+
+        let dx = player.center.x - self.position.x;
+        let dy = player.center.y - self.position.y;
+        let distance = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+
+        // Phase 3 : charges rapides vers la position du joueur
+        if self.phase == BossPhase::Desperate && self.charge_timer <= 0.0 && self.charge_time_left <= 0.0 {
+            self.charge_direction = Point2 { x: dx / distance, y: dy / distance };
+            self.charge_time_left = CHARGE_DURATION;
+            self.charge_timer = 5.0;
+        }
+
+        let (direction, speed) = if self.charge_time_left > 0.0 {
+            self.charge_time_left -= dt;
+            (self.charge_direction, CHARGE_SPEED)
+        } else {
+            (Point2 { x: dx / distance, y: dy / distance }, self.phase.speed())
+        };
+
+        if distance > BOSS_RADIUS {
+            let delta = Point2 {
+                x: direction.x * speed * dt,
+                y: direction.y * speed * dt,
+            };
+            self.position = map.move_with_collisions(self.position, delta, BOSS_RADIUS);
+        }
+
+        if self.bite_timer <= 0.0 && Collider::circle(self.position, BOSS_RADIUS + BOSS_REACH).intersects(player) {
+            self.bite_timer = self.phase.bite_cooldown();
+            actions.push(BossAction::Hit { damage: 20, attack: "Morsure" });
+        }
+
+        // Boules de feu, en éventail de trois à partir de la phase 2
+        if self.fireball_timer <= 0.0 {
+            self.fireball_timer = if self.phase == BossPhase::Desperate { 1.8 } else { 2.5 };
+            let aim = dy.atan2(dx);
+            let spreads: &[f32] = match self.phase {
+                BossPhase::Awakened => &[0.0],
+                _ => &[-0.3, 0.0, 0.3],
+            };
+            for spread in spreads {
+                let angle = aim + spread;
+                actions.push(BossAction::Shoot(Projectile::new(
+                    self.position,
+                    Point2 { x: angle.cos(), y: angle.sin() },
+                    ProjectileKind::Fireball,
+                    ProjectileOwner::Enemy,
+                    FIREBALL_DAMAGE,
+                )));
+            }
+        }
+
+        // Phases 2 et 3 : balayage de queue autour du dragon
+        if self.phase != BossPhase::Awakened && self.sweep_timer <= 0.0 {
+            self.sweep_timer = 4.0;
+            if Collider::circle(self.position, SWEEP_RADIUS).intersects(player) {
+                actions.push(BossAction::Hit { damage: 15, attack: "Coup de queue" });
+            }
+        }
+
+        actions
+    }
+
+    fn update_phase(&mut self, actions: &mut Vec<BossAction>) {
+        let ratio = self.health as f32 / self.max_health as f32;
+        let phase = if ratio <= 1.0 / 3.0 {
+            BossPhase::Desperate
+        } else if ratio <= 2.0 / 3.0 {
+            BossPhase::Enraged
+        } else {
+            BossPhase::Awakened
+        };
+
+        if phase == self.phase {
+            return;
+        }
+
+        self.phase = phase;
+        actions.push(BossAction::PhaseChanged(phase));
+        match phase {
+            BossPhase::Enraged => actions.push(BossAction::Summon(EnemyType::Goblin, 2)),
+            BossPhase::Desperate => actions.push(BossAction::Summon(EnemyType::Orc, 2)),
+            BossPhase::Intro | BossPhase::Awakened => {}
+        }
+    }
+
+    pub fn is_vulnerable(&self) -> bool {
+        self.is_alive && self.phase != BossPhase::Intro
+    }
+
+    pub fn take_damage(&mut self, damage: i32) {
+        if !self.is_vulnerable() {
+            return;
+        }
+        self.health = (self.health - damage).max(0);
+        if self.health == 0 {
+            self.is_alive = false;
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        Collider::circle(self.position, BOSS_RADIUS)
+    }
+
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, sprite: Option<&Image>, game_time: f32) -> GameResult<()> {
+        if !self.is_alive {
+            return Ok(());
+        }
+
+        // Pendant l'intro le dragon clignote, puis rougit avec les phases
+        let color = match self.phase {
+            BossPhase::Intro => {
+                let alpha = ((game_time * 8.0).sin() * 0.4 + 0.6) * 255.0;
+                Color::from_rgba(255, 255, 255, alpha as u8)
+            }
+            BossPhase::Awakened => Color::WHITE,
+            BossPhase::Enraged => Color::from_rgb(255, 190, 150),
+            BossPhase::Desperate => Color::from_rgb(255, 120, 100),
+        };
+
+        if let Some(sprite) = sprite {
+            canvas.draw(
+                sprite,
+                DrawParam::default()
+                    .dest(self.position)
+                    .offset([0.5, 0.5])
+                    .scale([2.0, 2.0])
+                    .color(color),
+            );
+        } else {
+            let circle = Mesh::new_circle(
+                ctx,
+                graphics::DrawMode::fill(),
+                Point2 { x: 0.0, y: 0.0 },
+                BOSS_RADIUS,
+                0.1,
+                Color::from_rgba(180, 0, 0, (color.a * 255.0) as u8),
+            )?;
+            canvas.draw(&circle, DrawParam::default().dest(self.position));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use ggez::graphics::Rect;
+use ggez::mint::Point2;
+
+// Zone centrale où le héros peut bouger sans que la caméra ne suive
+const DEAD_ZONE_WIDTH: f32 = 160.0;
+const DEAD_ZONE_HEIGHT: f32 = 110.0;
+// Plus la valeur est grande, plus la caméra rattrape vite sa cible
+const SMOOTHING: f32 = 6.0;
+
+pub struct Camera {
+    // Centre de la vue, en coordonnées du monde
+    pub position: Point2<f32>,
+    viewport_width: f32,
+    viewport_height: f32,
+}
+
+impl Camera {
+    pub fn new(viewport_width: f32, viewport_height: f32) -> Self {
+        Camera {
+            position: Point2 { x: viewport_width / 2.0, y: viewport_height / 2.0 },
+            viewport_width,
+            viewport_height,
+        }
+    }
+
+    pub fn resize(&mut self, viewport_width: f32, viewport_height: f32) {
+        self.viewport_width = viewport_width;
+        self.viewport_height = viewport_height;
+    }
+
+    pub fn snap_to(&mut self, target: Point2<f32>, map_width: f32, map_height: f32) {
+        self.position = target;
+        self.clamp(map_width, map_height);
+    }
+
+    pub fn follow(&mut self, target: Point2<f32>, map_width: f32, map_height: f32, dt: f32) {
+        let mut desired = self.position;
+
+        let dx = target.x - self.position.x;
+        if dx > DEAD_ZONE_WIDTH / 2.0 {
+            desired.x = target.x - DEAD_ZONE_WIDTH / 2.0;
+        } else if dx < -DEAD_ZONE_WIDTH / 2.0 {
+            desired.x = target.x + DEAD_ZONE_WIDTH / 2.0;
+        }
+
+        let dy = target.y - self.position.y;
+        if dy > DEAD_ZONE_HEIGHT / 2.0 {
+            desired.y = target.y - DEAD_ZONE_HEIGHT / 2.0;
+        } else if dy < -DEAD_ZONE_HEIGHT / 2.0 {
+            desired.y = target.y + DEAD_ZONE_HEIGHT / 2.0;
+        }
+
+        // Lissage exponentiel, indépendant de la fréquence d'affichage
+        let blend = 1.0 - (-SMOOTHING * dt).exp();
+        self.position.x += (desired.x - self.position.x) * blend;
+        self.position.y += (desired.y - self.position.y) * blend;
+        self.clamp(map_width, map_height);
+    }
+
+    // Une carte plus petite que l'écran reste centrée
+    fn clamp(&mut self, map_width: f32, map_height: f32) {
+        self.position.x = clamp_axis(self.position.x, self.viewport_width, map_width);
+        self.position.y = clamp_axis(self.position.y, self.viewport_height, map_height);
+    }
+
+    pub fn view_rect(&self) -> Rect {
+        Rect::new(
+            self.position.x - self.viewport_width / 2.0,
+            self.position.y - self.viewport_height / 2.0,
+            self.viewport_width,
+            self.viewport_height,
+        )
+    }
+
+    pub fn is_visible(&self, point: Point2<f32>, margin: f32) -> bool {
+        let view = self.view_rect();
+        point.x + margin >= view.x && point.x - margin <= view.x + view.w &&
+            point.y + margin >= view.y && point.y - margin <= view.y + view.h
+    }
+
+    pub fn world_to_screen(&self, point: Point2<f32>) -> Point2<f32> {
+        let view = self.view_rect();
+        Point2 { x: point.x - view.x, y: point.y - view.y }
+    }
+}
+
+fn clamp_axis(center: f32, viewport: f32, map: f32) -> f32 {
+    if map <= viewport {
+        map / 2.0
+    } else {
+        center.clamp(viewport / 2.0, map - viewport / 2.0)
+    }
+}
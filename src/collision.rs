@@ -0,0 +1,230 @@
+use ggez::mint::Point2;
+
+const KNOCKBACK_DAMPING: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Circle { radius: f32 },
+    Aabb { half_width: f32, half_height: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collider {
+    pub center: Point2<f32>,
+    pub shape: Shape,
+}
+
+impl Collider {
+    pub fn circle(center: Point2<f32>, radius: f32) -> Self {
+        Collider {
+            center,
+            shape: Shape::Circle { radius },
+        }
+    }
+
+    pub fn aabb(center: Point2<f32>, width: f32, height: f32) -> Self {
+        Collider {
+            center,
+            shape: Shape::Aabb {
+                half_width: width / 2.0,
+                half_height: height / 2.0,
+            },
+        }
+    }
+
+    pub fn intersects(&self, other: &Collider) -> bool {
+        self.penetration(other).is_some()
+    }
+
+    // Vecteur minimal à appliquer à `self` pour ne plus chevaucher `other`
+    pub fn penetration(&self, other: &Collider) -> Option<Point2<f32>> {
+        match (self.shape, other.shape) {
+            (Shape::Circle { radius: a }, Shape::Circle { radius: b }) => {
+                circle_circle(self.center, a, other.center, b)
+            }
+            (Shape::Circle { radius }, Shape::Aabb { half_width, half_height }) => {
+                circle_aabb(self.center, radius, other.center, half_width, half_height)
+            }
+            (Shape::Aabb { half_width, half_height }, Shape::Circle { radius }) => {
+                circle_aabb(other.center, radius, self.center, half_width, half_height)
+                    .map(|v| Point2 { x: -v.x, y: -v.y })
+            }
+            (
+                Shape::Aabb { half_width: aw, half_height: ah },
+                Shape::Aabb { half_width: bw, half_height: bh },
+            ) => aabb_aabb(self.center, aw, ah, other.center, bw, bh),
+        }
+    }
+}
+
+fn circle_circle(a: Point2<f32>, ra: f32, b: Point2<f32>, rb: f32) -> Option<Point2<f32>> {
+    let dx = a.x - b.x;
+    let dy = a.y - b.y;
+    let distance_sq = dx * dx + dy * dy;
+    let min_distance = ra + rb;
+
+    if distance_sq >= min_distance * min_distance {
+        return None;
+    }
+
+    let distance = distance_sq.sqrt();
+    if distance <= f32::EPSILON {
+        return Some(Point2 { x: 0.0, y: -min_distance });
+    }
+
+    let depth = min_distance - distance;
+    Some(Point2 {
+        x: dx / distance * depth,
+        y: dy / distance * depth,
+    })
+}
+
+fn circle_aabb(
+    center: Point2<f32>,
+    radius: f32,
+    box_center: Point2<f32>,
+    half_width: f32,
+    half_height: f32,
+) -> Option<Point2<f32>> {
+    let local_x = center.x - box_center.x;
+    let local_y = center.y - box_center.y;
+    let closest_x = local_x.clamp(-half_width, half_width);
+    let closest_y = local_y.clamp(-half_height, half_height);
+
+    let dx = local_x - closest_x;
+    let dy = local_y - closest_y;
+    let distance_sq = dx * dx + dy * dy;
+
+    if distance_sq > f32::EPSILON {
+        if distance_sq >= radius * radius {
+            return None;
+        }
+        let distance = distance_sq.sqrt();
+        let depth = radius - distance;
+        return Some(Point2 {
+            x: dx / distance * depth,
+            y: dy / distance * depth,
+        });
+    }
+
+    // Centre du cercle à l'intérieur de la boîte : on sort par l'axe le plus proche
+    let push_x = half_width - local_x.abs() + radius;
+    let push_y = half_height - local_y.abs() + radius;
+    if push_x < push_y {
+        Some(Point2 { x: push_x.copysign(local_x), y: 0.0 })
+    } else {
+        Some(Point2 { x: 0.0, y: push_y.copysign(local_y) })
+    }
+}
+
+fn aabb_aabb(
+    a: Point2<f32>,
+    aw: f32,
+    ah: f32,
+    b: Point2<f32>,
+    bw: f32,
+    bh: f32,
+) -> Option<Point2<f32>> {
+    let dx = a.x - b.x;
+    let dy = a.y - b.y;
+    let overlap_x = aw + bw - dx.abs();
+    let overlap_y = ah + bh - dy.abs();
+
+    if overlap_x <= 0.0 || overlap_y <= 0.0 {
+        return None;
+    }
+
+    if overlap_x < overlap_y {
+        Some(Point2 { x: overlap_x.copysign(dx), y: 0.0 })
+    } else {
+        Some(Point2 { x: 0.0, y: overlap_y.copysign(dy) })
+    }
+}
+
+// Impulsion qui repousse `target` à l'opposé de `from`
+pub fn knockback_impulse(from: Point2<f32>, target: Point2<f32>, strength: f32) -> Point2<f32> {
+    let dx = target.x - from.x;
+    let dy = target.y - from.y;
+    let length = (dx * dx + dy * dy).sqrt();
+    if length <= f32::EPSILON {
+        return Point2 { x: 0.0, y: -strength };
+    }
+    Point2 {
+        x: dx / length * strength,
+        y: dy / length * strength,
+    }
+}
+
+// Amortit une impulsion de recul, en tenant compte du pas de temps
+pub fn damp_knockback(knockback: Point2<f32>, dt: f32) -> Point2<f32> {
+    let factor = (-KNOCKBACK_DAMPING * dt).exp();
+    if (knockback.x * knockback.x + knockback.y * knockback.y) * factor * factor < 1.0 {
+        return Point2 { x: 0.0, y: 0.0 };
+    }
+    Point2 {
+        x: knockback.x * factor,
+        y: knockback.y * factor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, y: f32) -> Point2<f32> {
+        Point2 { x, y }
+    }
+
+    #[test]
+    fn circles_apart_do_not_collide() {
+        let a = Collider::circle(point(0.0, 0.0), 10.0);
+        let b = Collider::circle(point(25.0, 0.0), 10.0);
+        assert_eq!(a.penetration(&b), None);
+    }
+
+    #[test]
+    fn overlapping_circles_push_apart_along_centers() {
+        let a = Collider::circle(point(0.0, 0.0), 10.0);
+        let b = Collider::circle(point(15.0, 0.0), 10.0);
+        let push = a.penetration(&b).unwrap();
+        assert!((push.x + 5.0).abs() < 1e-4);
+        assert!(push.y.abs() < 1e-4);
+    }
+
+    #[test]
+    fn circle_touching_box_side_is_pushed_out() {
+        let circle = Collider::circle(point(0.0, 18.0), 10.0);
+        let aabb = Collider::aabb(point(0.0, 0.0), 40.0, 20.0);
+        let push = circle.penetration(&aabb).unwrap();
+        assert!(push.x.abs() < 1e-4);
+        assert!((push.y - 2.0).abs() < 1e-4);
+
+        // Dans l'autre sens, la boîte est repoussée à l'opposé
+        let back = aabb.penetration(&circle).unwrap();
+        assert!((back.y + 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn circle_inside_box_leaves_by_nearest_side() {
+        let circle = Collider::circle(point(15.0, 0.0), 5.0);
+        let aabb = Collider::aabb(point(0.0, 0.0), 40.0, 40.0);
+        let push = circle.penetration(&aabb).unwrap();
+        assert!((push.x - 10.0).abs() < 1e-4);
+        assert_eq!(push.y, 0.0);
+    }
+
+    #[test]
+    fn circle_near_box_corner_does_not_collide() {
+        let circle = Collider::circle(point(28.0, 28.0), 10.0);
+        let aabb = Collider::aabb(point(0.0, 0.0), 40.0, 40.0);
+        assert_eq!(circle.penetration(&aabb), None);
+    }
+
+    #[test]
+    fn boxes_push_along_smallest_overlap() {
+        let a = Collider::aabb(point(0.0, 0.0), 20.0, 20.0);
+        let b = Collider::aabb(point(16.0, 4.0), 20.0, 20.0);
+        assert_eq!(a.penetration(&b), Some(point(-4.0, 0.0)));
+        assert_eq!(a.penetration(&Collider::aabb(point(30.0, 0.0), 20.0, 20.0)), None);
+    }
+}
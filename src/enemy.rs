@@ -1,9 +1,25 @@
 use ggez::{Context, GameResult, graphics};
-use ggez::graphics::{Canvas, DrawParam, Color, Image, Mesh};
+use ggez::graphics::{Canvas, DrawParam, Color, Mesh};
 use ggez::mint::Point2;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+use crate::animation::{AnimationFrame, Clip, SpriteSheet};
+use crate::collision::{self, Collider};
+use crate::map::Map;
+use crate::pathfinding::{NavGrid, NAV_CELL_SIZE};
+use crate::player::Direction;
+use crate::projectile::{Projectile, ProjectileKind, ProjectileOwner};
+
+pub const ENEMY_RADIUS: f32 = 12.0;
+pub const ENEMY_ATTACK_RADIUS: f32 = 15.0;
+const REPATH_INTERVAL: f32 = 0.5;
+const PATROL_RADIUS: f32 = 60.0;
+const STAGGER_DURATION: f32 = 0.35;
+const ATTACK_ANIMATION: f32 = 0.25;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Enemy {
+    pub id: u32,
     pub position: Point2<f32>,
     pub health: i32,
     pub max_health: i32,
@@ -11,112 +27,447 @@ pub struct Enemy {
     pub speed: f32,
     pub attack_timer: f32,
     pub is_alive: bool,
-    sprite: Option<Image>,
+    pub state: AiState,
+    home: Point2<f32>,
+    patrol_index: usize,
+    pub stagger_timer: f32,
+    knockback: Point2<f32>,
+    pub facing: Direction,
+    pub is_moving: bool,
+    // Chemin en cache, le prochain point de passage est en fin de vecteur
+    #[serde(skip)]
+    path: Vec<Point2<f32>>,
+    #[serde(skip)]
+    path_goal: Option<usize>,
+    #[serde(skip)]
+    repath_timer: f32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum EnemyType {
     Goblin,
+    Archer,
     Orc,
     Dragon,
 }
 
-impl Enemy {
-    pub fn new(ctx: &mut Context, x: f32, y: f32, enemy_type: EnemyType) -> Self {
-        let (health, speed) = match enemy_type {
-            EnemyType::Goblin => (30, 80.0),
-            EnemyType::Orc => (60, 50.0),
-            EnemyType::Dragon => (120, 40.0),
-        };
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AiState {
+    Patrol,
+    Chase,
+    Attack,
+    Return,
+    Flee,
+}
 
-        let sprite_path = match enemy_type {
-            EnemyType::Goblin => "/goblin.png",
-            EnemyType::Orc => "/orc.png",
-            EnemyType::Dragon => "/dragon.png",
-        };
-        
-        let sprite = Image::from_path(ctx, sprite_path).ok();
+pub struct EnemyProfile {
+    pub health: i32,
+    pub speed: f32,
+    pub damage: i32,
+    pub attack_cooldown: f32,
+    // Distance à laquelle l'ennemi repère le joueur
+    pub detection_radius: f32,
+    // Distance maximale à son poste avant d'abandonner la poursuite
+    pub leash_radius: f32,
+    // Fraction de vie sous laquelle l'ennemi s'enfuit
+    pub flee_health_ratio: Option<f32>,
+    pub ranged: Option<RangedAttack>,
+    // Expérience gagnée par le héros en l'éliminant
+    pub xp_reward: u32,
+}
+
+pub struct RangedAttack {
+    pub kind: ProjectileKind,
+    pub range: f32,
+}
+
+pub enum EnemyAttack {
+    Melee(i32),
+    Ranged(Projectile),
+}
+
+const GOBLIN_PROFILE: EnemyProfile = EnemyProfile {
+    health: 30,
+    speed: 80.0,
+    damage: 5,
+    attack_cooldown: 1.5,
+    detection_radius: 180.0,
+    leash_radius: 320.0,
+    flee_health_ratio: Some(0.35),
+    ranged: None,
+    xp_reward: 10,
+};
+
+const ARCHER_PROFILE: EnemyProfile = EnemyProfile {
+    health: 20,
+    speed: 70.0,
+    damage: 6,
+    attack_cooldown: 2.0,
+    detection_radius: 220.0,
+    leash_radius: 320.0,
+    flee_health_ratio: Some(0.35),
+    ranged: Some(RangedAttack {
+        kind: ProjectileKind::Arrow,
+        range: 170.0,
+    }),
+    xp_reward: 12,
+};
+
+const ORC_PROFILE: EnemyProfile = EnemyProfile {
+    health: 60,
+    speed: 50.0,
+    damage: 10,
+    attack_cooldown: 1.5,
+    detection_radius: 150.0,
+    leash_radius: 260.0,
+    flee_health_ratio: None,
+    ranged: None,
+    xp_reward: 25,
+};
+
+const DRAGON_PROFILE: EnemyProfile = EnemyProfile {
+    health: 120,
+    speed: 40.0,
+    damage: 20,
+    attack_cooldown: 1.5,
+    detection_radius: 260.0,
+    leash_radius: 400.0,
+    flee_health_ratio: None,
+    ranged: None,
+    xp_reward: 60,
+};
+
+impl EnemyType {
+    pub fn profile(self) -> &'static EnemyProfile {
+        match self {
+            EnemyType::Goblin => &GOBLIN_PROFILE,
+            EnemyType::Archer => &ARCHER_PROFILE,
+            EnemyType::Orc => &ORC_PROFILE,
+            EnemyType::Dragon => &DRAGON_PROFILE,
+        }
+    }
+
+    // Tous les ennemis partagent la même planche, teintée et mise à l'échelle par type
+    pub fn sprite_tint(self) -> Color {
+        match self {
+            EnemyType::Goblin => Color::WHITE,
+            EnemyType::Archer => Color::from_rgb(220, 255, 200),
+            EnemyType::Orc => Color::from_rgb(200, 170, 170),
+            EnemyType::Dragon => Color::from_rgb(150, 100, 100),
+        }
+    }
+
+    pub fn sprite_scale(self) -> f32 {
+        match self {
+            EnemyType::Goblin | EnemyType::Archer => 0.3,
+            EnemyType::Orc => 0.38,
+            EnemyType::Dragon => 0.45,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EnemyType::Goblin => "Goblin",
+            EnemyType::Archer => "Archer gobelin",
+            EnemyType::Orc => "Orc",
+            EnemyType::Dragon => "Dragon",
+        }
+    }
+}
+
+impl Enemy {
+    pub fn new(id: u32, x: f32, y: f32, enemy_type: EnemyType) -> Self {
+        let profile = enemy_type.profile();
 
         Enemy {
+            id,
             position: Point2 { x, y },
-            health,
-            max_health: health,
+            health: profile.health,
+            max_health: profile.health,
             enemy_type,
-            speed,
+            speed: profile.speed,
             attack_timer: 0.0,
             is_alive: true,
-            sprite,
+            state: AiState::Patrol,
+            home: Point2 { x, y },
+            patrol_index: 0,
+            stagger_timer: 0.0,
+            knockback: Point2 { x: 0.0, y: 0.0 },
+            facing: Direction::Down,
+            is_moving: false,
+            path: Vec::new(),
+            path_goal: None,
+            repath_timer: 0.0,
         }
     }
 
-    pub fn update(&mut self, dt: f32, player_pos: Point2<f32>) {
+    pub fn update(&mut self, dt: f32, player: &Collider, map: &Map, nav: &NavGrid) {
         if !self.is_alive {
             return;
         }
 
-        let dx = player_pos.x - self.position.x;
-        let dy = player_pos.y - self.position.y;
-        let distance = (dx * dx + dy * dy).sqrt();
+        if self.attack_timer > 0.0 {
+            self.attack_timer -= dt;
+        }
+        self.repath_timer -= dt;
+        self.is_moving = false;
+
+        // Sonné : l'ennemi subit le recul et ne fait rien d'autre
+        if self.is_staggered() {
+            self.stagger_timer -= dt;
+            let delta = Point2 {
+                x: self.knockback.x * dt,
+                y: self.knockback.y * dt,
+            };
+            self.position = map.move_with_collisions(self.position, delta, ENEMY_RADIUS);
+            self.knockback = collision::damp_knockback(self.knockback, dt);
+            return;
+        }
 
-        if distance > 10.0 {
-            let direction_x = dx / distance;
-            let direction_y = dy / distance;
+        let state = self.next_state(player);
+        if state != self.state {
+            // Nouvelle destination : le chemin en cache n'est plus valable
+            self.path.clear();
+            self.repath_timer = 0.0;
+            self.state = state;
+        }
 
-            self.position.x += direction_x * self.speed * dt;
-            self.position.y += direction_y * self.speed * dt;
+        match self.state {
+            AiState::Patrol => {
+                // On passe au point suivant une fois arrivé, ou si le point tombe dans un obstacle
+                let mut target = self.patrol_point();
+                for _ in 0..4 {
+                    if nav.is_walkable(target) && distance(self.position, target) >= NAV_CELL_SIZE {
+                        break;
+                    }
+                    self.patrol_index = (self.patrol_index + 1) % 4;
+                    target = self.patrol_point();
+                }
+                if nav.is_walkable(target) {
+                    self.move_along_path(dt, target, map, nav, 0.5);
+                }
+            }
+            AiState::Chase => {
+                self.move_along_path(dt, player.center, map, nav, 1.0);
+            }
+            AiState::Attack => {}
+            AiState::Return => {
+                self.move_along_path(dt, self.home, map, nav, 1.0);
+            }
+            AiState::Flee => {
+                let away = Point2 {
+                    x: self.position.x - player.center.x,
+                    y: self.position.y - player.center.y,
+                };
+                let length = distance(self.position, player.center).max(f32::EPSILON);
+                let target = Point2 {
+                    x: self.position.x + away.x / length * NAV_CELL_SIZE,
+                    y: self.position.y + away.y / length * NAV_CELL_SIZE,
+                };
+                self.move_towards(dt, target, map, 1.0);
+            }
         }
+    }
 
-        if self.attack_timer > 0.0 {
-            self.attack_timer -= dt;
+    fn next_state(&mut self, player: &Collider) -> AiState {
+        let profile = self.enemy_type.profile();
+        let player_distance = distance(self.position, player.center);
+        let sees_player = player_distance < profile.detection_radius;
+
+        let wounded = profile.flee_health_ratio
+            .is_some_and(|ratio| (self.health as f32) < self.max_health as f32 * ratio);
+        if wounded {
+            if sees_player {
+                return AiState::Flee;
+            }
+            if self.state == AiState::Flee {
+                // Hors de vue : il se pose là où il a fui
+                self.home = self.position;
+                return AiState::Patrol;
+            }
+        }
+
+        match self.state {
+            AiState::Patrol | AiState::Return if sees_player => AiState::Chase,
+            AiState::Patrol => AiState::Patrol,
+            AiState::Return => {
+                if distance(self.position, self.home) < NAV_CELL_SIZE {
+                    AiState::Patrol
+                } else {
+                    AiState::Return
+                }
+            }
+            AiState::Chase | AiState::Attack | AiState::Flee => {
+                let too_far_from_home = distance(self.position, self.home) > profile.leash_radius;
+                let lost_player = player_distance > profile.detection_radius * 1.5;
+                if too_far_from_home || lost_player {
+                    AiState::Return
+                } else if self.is_in_attack_range(player) {
+                    AiState::Attack
+                } else {
+                    AiState::Chase
+                }
+            }
+        }
+    }
+
+    fn patrol_point(&self) -> Point2<f32> {
+        let (dx, dy) = match self.patrol_index {
+            0 => (PATROL_RADIUS, 0.0),
+            1 => (0.0, PATROL_RADIUS),
+            2 => (-PATROL_RADIUS, 0.0),
+            _ => (0.0, -PATROL_RADIUS),
+        };
+        Point2 {
+            x: self.home.x + dx,
+            y: self.home.y + dy,
+        }
+    }
+
+    fn move_along_path(&mut self, dt: f32, goal: Point2<f32>, map: &Map, nav: &NavGrid, speed_factor: f32) {
+        if distance(self.position, goal) <= 10.0 {
+            return;
         }
+        let target = self.next_waypoint(goal, nav);
+        self.move_towards(dt, target, map, speed_factor);
     }
 
-    pub fn take_damage(&mut self, damage: i32) {
+    fn move_towards(&mut self, dt: f32, target: Point2<f32>, map: &Map, speed_factor: f32) {
+        let dx = target.x - self.position.x;
+        let dy = target.y - self.position.y;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length <= f32::EPSILON {
+            return;
+        }
+
+        let speed = self.speed * speed_factor * map.speed_multiplier_at(self.position);
+        let delta = Point2 {
+            x: dx / length * speed * dt,
+            y: dy / length * speed * dt,
+        };
+        self.position = map.move_with_collisions(self.position, delta, ENEMY_RADIUS);
+        self.is_moving = true;
+        self.facing = if dx.abs() > dy.abs() {
+            if dx < 0.0 { Direction::Left } else { Direction::Right }
+        } else if dy < 0.0 {
+            Direction::Up
+        } else {
+            Direction::Down
+        };
+    }
+
+    fn next_waypoint(&mut self, goal: Point2<f32>, nav: &NavGrid) -> Point2<f32> {
+        // On ne recalcule que périodiquement, et seulement si la cible a changé de cellule
+        let goal_cell = nav.cell_of(goal);
+        let stale = goal_cell != self.path_goal || self.path.is_empty();
+        if stale && self.repath_timer <= 0.0 {
+            self.path = nav.find_path(self.position, goal).unwrap_or_default();
+            self.path.reverse();
+            self.path_goal = goal_cell;
+            self.repath_timer = REPATH_INTERVAL;
+        }
+
+        self.follow_path(goal)
+    }
+
+    fn follow_path(&mut self, goal: Point2<f32>) -> Point2<f32> {
+        while let Some(&waypoint) = self.path.last() {
+            if distance(self.position, waypoint) > NAV_CELL_SIZE / 2.0 {
+                break;
+            }
+            self.path.pop();
+        }
+
+        self.path.last().copied().unwrap_or(goal)
+    }
+
+    pub fn take_damage(&mut self, damage: i32, from: Point2<f32>, knockback: f32) {
         self.health -= damage;
         if self.health <= 0 {
             self.is_alive = false;
+            return;
         }
+
+        // Le coup interrompt l'attaque en préparation
+        self.stagger_timer = STAGGER_DURATION;
+        self.attack_timer = self.attack_timer.max(self.enemy_type.profile().attack_cooldown / 2.0);
+        self.knockback = collision::knockback_impulse(from, self.position, knockback);
+    }
+
+    pub fn is_staggered(&self) -> bool {
+        self.stagger_timer > 0.0
     }
 
     pub fn can_attack(&self) -> bool {
-        self.attack_timer <= 0.0 && self.is_alive
+        self.attack_timer <= 0.0 && self.is_alive && self.state != AiState::Flee && !self.is_staggered()
     }
 
-    pub fn attack(&mut self) -> i32 {
-        self.attack_timer = 1.5;
-        
-        match self.enemy_type {
-            EnemyType::Goblin => 5,
-            EnemyType::Orc => 10,
-            EnemyType::Dragon => 20,
+    pub fn attack(&mut self, target: Point2<f32>) -> EnemyAttack {
+        let profile = self.enemy_type.profile();
+        self.attack_timer = profile.attack_cooldown;
+
+        match &profile.ranged {
+            Some(ranged) => {
+                let direction = Point2 {
+                    x: target.x - self.position.x,
+                    y: target.y - self.position.y,
+                };
+                EnemyAttack::Ranged(Projectile::new(
+                    self.position,
+                    direction,
+                    ranged.kind,
+                    ProjectileOwner::Enemy,
+                    profile.damage,
+                ))
+            }
+            None => EnemyAttack::Melee(profile.damage),
         }
     }
 
-    pub fn is_in_attack_range(&self, player_pos: Point2<f32>) -> bool {
-        let dx = player_pos.x - self.position.x;
-        let dy = player_pos.y - self.position.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-        
-        distance < 30.0
+    pub fn collider(&self) -> Collider {
+        Collider::circle(self.position, ENEMY_RADIUS)
     }
 
-    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult<()> {
+    pub fn is_in_attack_range(&self, player: &Collider) -> bool {
+        match &self.enemy_type.profile().ranged {
+            Some(ranged) => distance(self.position, player.center) <= ranged.range,
+            None => Collider::circle(self.position, ENEMY_ATTACK_RADIUS).intersects(player),
+        }
+    }
+
+    pub fn animation_clip(&self) -> Clip {
+        let just_attacked = self.attack_timer > self.enemy_type.profile().attack_cooldown - ATTACK_ANIMATION;
+        if !self.is_alive {
+            Clip::Death
+        } else if self.is_staggered() {
+            Clip::Hurt
+        } else if just_attacked {
+            Clip::Attack
+        } else if self.is_moving {
+            Clip::Walk(self.facing)
+        } else {
+            Clip::Idle
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, sheet: Option<&SpriteSheet>, frame: AnimationFrame) -> GameResult<()> {
         if !self.is_alive {
             return Ok(());
         }
 
-        if let Some(sprite) = &self.sprite {
-            canvas.draw(
-                sprite,
-                DrawParam::default()
-                    .dest(self.position)
-                    .offset([0.5, 0.5])
-                    .color(Color::WHITE),
+        if let Some(sheet) = sheet {
+            let tint = if self.is_staggered() { Color::from_rgb(255, 150, 150) } else { self.enemy_type.sprite_tint() };
+            sheet.draw(
+                canvas,
+                frame,
+                DrawParam::default().dest(self.position).color(tint),
+                self.enemy_type.sprite_scale(),
             );
         } else {
            
             let color = match self.enemy_type {
                 EnemyType::Goblin => Color::from_rgb(0, 150, 0),
+                EnemyType::Archer => Color::from_rgb(100, 180, 60),
                 EnemyType::Orc => Color::from_rgb(150, 75, 0),
                 EnemyType::Dragon => Color::from_rgb(150, 0, 0),
             };
@@ -125,7 +476,7 @@ impl Enemy {
                 ctx,
                 graphics::DrawMode::fill(),
                 Point2 { x: 0.0, y: 0.0 },
-                12.0,
+                ENEMY_RADIUS,
                 0.1,
                 color,
             )?;
@@ -146,4 +497,10 @@ impl Enemy {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+fn distance(a: Point2<f32>, b: Point2<f32>) -> f32 {
+    let dx = a.x - b.x;
+    let dy = a.y - b.y;
+    (dx * dx + dy * dy).sqrt()
+}
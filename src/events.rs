@@ -0,0 +1,348 @@
+use std::collections::VecDeque;
+#[cfg(feature = "audio")]
+use std::collections::HashMap;
+
+use ggez::Context;
+
+use crate::boss::BossPhase;
+use crate::enemy::EnemyType;
+use crate::items::ItemType;
+use crate::player::StatChoice;
+use crate::projectile::ProjectileKind;
+use crate::weapon::WeaponKind;
+
+// Ce qui s'est passé pendant une frame de simulation. Le monde les empile,
+// le jeu les distribue ensuite à chaque abonné (console, interface, statistiques)
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    GameStarted { seed: u64, artifacts: u32 },
+    ItemCollected(ItemType),
+    ArtifactCollected { collected: u32, total: u32 },
+    // Objet utilisé sans effet, qui reste dans l'inventaire
+    ItemKept(ItemType),
+    Healed { from: i32, to: i32 },
+    WeaponEquipped { weapon: WeaponKind, previous: WeaponKind },
+    BombExploded { enemies_hit: u32 },
+    Purchased { offer: &'static str, price: u32, coins_left: u32 },
+    PurchaseRefused { offer: &'static str, reason: &'static str },
+    StatChosen(StatChoice),
+    XpGained { amount: u32, xp: u32, next_level: u32 },
+    LevelUp { level: u32 },
+    DamageDealt { target: DamageTarget, cause: DamageCause, amount: i32, health_before: i32, health_after: i32 },
+    AttackLanded { hits: u32 },
+    EnemySpawned(EnemyType),
+    EnemyKilled(EnemyType),
+    PlayerDamaged { source: DamageSource, amount: i32, health_before: i32, health_after: i32 },
+    PlayerDied,
+    BossSpawned,
+    BossPhaseChanged(BossPhase),
+    BossSummoned,
+    BossDefeated,
+    Victory { time: f32, coins: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageTarget {
+    Enemy(EnemyType),
+    Boss,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageCause {
+    Melee,
+    Projectile(ProjectileKind),
+    Bomb,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageSource {
+    Enemy(EnemyType),
+    // Nom de l'attaque du Dragon
+    Boss(&'static str),
+    Projectile(ProjectileKind),
+}
+
+impl DamageTarget {
+    fn name(self) -> &'static str {
+        match self {
+            DamageTarget::Enemy(enemy_type) => enemy_type.name(),
+            DamageTarget::Boss => "Dragon",
+        }
+    }
+}
+
+impl GameEvent {
+    // Message pour le joueur, éventuellement sur plusieurs lignes ; None si l'événement ne se raconte pas
+    pub fn describe(&self) -> Option<String> {
+        let text = match *self {
+            GameEvent::GameStarted { seed, artifacts } => format!(
+                "Nouvelle partie lancée ! (graine: {})\nObjectif: Collecter {} artefacts pour affronter le Dragon !",
+                seed, artifacts
+            ),
+            GameEvent::ItemCollected(ItemType::Coin) => "Pièce collectée !".to_string(),
+            GameEvent::ItemCollected(item_type) => format!("Inventaire : +1 {}", item_type.name()),
+            GameEvent::ArtifactCollected { collected, total } => format!(
+                "ARTEFACT COLLECTÉ ! ({}/{}) - Plus que {} restant(s)",
+                collected, total, total.saturating_sub(collected)
+            ),
+            GameEvent::ItemKept(ItemType::Potion) => "Santé déjà au maximum, la potion est conservée".to_string(),
+            GameEvent::ItemKept(ItemType::Key) => "Aucune serrure à proximité".to_string(),
+            GameEvent::ItemKept(item_type) => format!("Objet conservé : {}", item_type.name()),
+            GameEvent::Healed { from, to } => format!("Potion utilisée ! Nour: {} → {}", from, to),
+            GameEvent::WeaponEquipped { weapon, previous } => format!(
+                "Arme équipée : {} (ancienne : {})",
+                weapon.definition().name, previous.definition().name
+            ),
+            GameEvent::BombExploded { enemies_hit } => format!("BOUM ! {} ennemi(s) pris dans l'explosion", enemies_hit),
+            GameEvent::Purchased { offer, price, coins_left } => {
+                format!("Achat : {} pour {} pièces (reste: {})", offer, price, coins_left)
+            }
+            GameEvent::PurchaseRefused { offer, reason } => format!("Achat impossible ({}) : {}", offer, reason),
+            GameEvent::StatChosen(choice) => format!("Amélioration choisie : {}", choice.label()),
+            GameEvent::XpGained { amount, xp, next_level } => format!("+{} XP ({}/{})", amount, xp, next_level),
+            GameEvent::LevelUp { level } => format!(
+                "NIVEAU {} ! Choisissez une amélioration (1: {}, 2: {}, 3: {})",
+                level, StatChoice::MaxHealth.label(), StatChoice::Speed.label(), StatChoice::Damage.label()
+            ),
+            GameEvent::DamageDealt { target, cause, amount, health_before, health_after } => match cause {
+                DamageCause::Melee => format!(
+                    "{} touché ! -{} nour ({} → {})",
+                    target.name(), amount, health_before, health_after
+                ),
+                DamageCause::Projectile(kind) => format!(
+                    "{} sur {} ! -{} nour ({} PV restants)",
+                    match kind {
+                        ProjectileKind::Arrow => "Flèche",
+                        ProjectileKind::Fireball => "Boule de feu",
+                    },
+                    target.name(), amount, health_after.max(0)
+                ),
+                DamageCause::Bomb => format!("{} pris dans l'explosion ! -{} nour", target.name(), amount),
+            },
+            GameEvent::AttackLanded { hits } => format!("Attaque réussie ! {} ennemi(s) touché(s)", hits),
+            GameEvent::EnemySpawned(EnemyType::Dragon) => "Un dragon sauvage apparaît !".to_string(),
+            GameEvent::EnemySpawned(_) => return None,
+            GameEvent::EnemyKilled(enemy_type) => format!("{} vaincu !", enemy_type.name()),
+            GameEvent::PlayerDamaged { source, amount, health_before, health_after } => {
+                let attacker = match source {
+                    DamageSource::Enemy(enemy_type) => format!("{} vous attaque !", enemy_type.name()),
+                    DamageSource::Boss(attack) => format!("{} du Dragon !", attack),
+                    DamageSource::Projectile(ProjectileKind::Arrow) => "Touché par une flèche !".to_string(),
+                    DamageSource::Projectile(ProjectileKind::Fireball) => "Touché par une boule de feu !".to_string(),
+                };
+                format!("{} -{} PV ({} → {})", attacker, amount, health_before, health_after)
+            }
+            GameEvent::PlayerDied => "GAME OVER! Le héros est tombé au combat...".to_string(),
+            GameEvent::BossSpawned => {
+                "Tous les artefacts collectés ! Le boss final arrive...\nLE BOSS DRAGON APPARAÎT !\nAffrontez-le pour gagner la partie !".to_string()
+            }
+            GameEvent::BossPhaseChanged(BossPhase::Awakened) => "Le Dragon s'éveille ! Le combat commence !".to_string(),
+            GameEvent::BossPhaseChanged(phase) => format!("Le Dragon entre en {} !", phase.label()),
+            GameEvent::BossSummoned => "Le Dragon appelle des renforts !".to_string(),
+            GameEvent::BossDefeated => "Le Dragon est terrassé !".to_string(),
+            GameEvent::Victory { time, coins } => format!(
+                "VICTOIRE ! Vous avez sauvé le royaume !\nTemps total: {:.1} secondes\nPièces collectées: {}",
+                time, coins
+            ),
+        };
+        Some(text)
+    }
+}
+
+// Bruitages joués par AudioCues, rangés dans resources/sounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundCue {
+    Coin,
+    Pickup,
+    Heal,
+    Hit,
+    Hurt,
+    Explosion,
+    Purchase,
+    LevelUp,
+    BossRoar,
+    Victory,
+    GameOver,
+}
+
+impl SoundCue {
+    #[cfg_attr(not(feature = "audio"), allow(dead_code))]
+    pub fn path(self) -> &'static str {
+        match self {
+            SoundCue::Coin => "/sounds/coin.ogg",
+            SoundCue::Pickup => "/sounds/pickup.ogg",
+            SoundCue::Heal => "/sounds/heal.ogg",
+            SoundCue::Hit => "/sounds/hit.ogg",
+            SoundCue::Hurt => "/sounds/hurt.ogg",
+            SoundCue::Explosion => "/sounds/explosion.ogg",
+            SoundCue::Purchase => "/sounds/purchase.ogg",
+            SoundCue::LevelUp => "/sounds/level_up.ogg",
+            SoundCue::BossRoar => "/sounds/boss_roar.ogg",
+            SoundCue::Victory => "/sounds/victory.ogg",
+            SoundCue::GameOver => "/sounds/game_over.ogg",
+        }
+    }
+}
+
+pub trait EventSubscriber {
+    fn handle(&mut self, event: &GameEvent);
+}
+
+// Reprend les messages affichés jusqu'ici dans le terminal
+pub struct ConsoleLogger;
+
+impl EventSubscriber for ConsoleLogger {
+    fn handle(&mut self, event: &GameEvent) {
+        if let Some(text) = event.describe() {
+            for line in text.lines() {
+                println!(" {}", line);
+            }
+        }
+    }
+}
+
+const FEED_CAPACITY: usize = 5;
+const FEED_DURATION: f32 = 4.0;
+
+// Derniers messages importants, affichés quelques secondes à l'écran
+#[derive(Default)]
+pub struct MessageFeed {
+    messages: VecDeque<(String, f32)>,
+}
+
+impl MessageFeed {
+    pub fn update(&mut self, dt: f32) {
+        for (_, remaining) in &mut self.messages {
+            *remaining -= dt;
+        }
+        self.messages.retain(|(_, remaining)| *remaining > 0.0);
+    }
+
+    // Du plus ancien au plus récent, avec l'opacité restante
+    pub fn messages(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.messages.iter().map(|(text, remaining)| (text.as_str(), remaining.min(1.0)))
+    }
+}
+
+impl EventSubscriber for MessageFeed {
+    fn handle(&mut self, event: &GameEvent) {
+        let shown = match event {
+            GameEvent::GameStarted { .. } => {
+                self.messages.clear();
+                false
+            }
+            GameEvent::ItemCollected(item_type) => *item_type != ItemType::Coin,
+            GameEvent::ArtifactCollected { .. } |
+            GameEvent::ItemKept(_) |
+            GameEvent::WeaponEquipped { .. } |
+            GameEvent::Purchased { .. } |
+            GameEvent::PurchaseRefused { .. } |
+            GameEvent::LevelUp { .. } |
+            GameEvent::EnemyKilled(_) |
+            GameEvent::BossSpawned |
+            GameEvent::BossPhaseChanged(_) |
+            GameEvent::BossSummoned |
+            GameEvent::BossDefeated => true,
+            _ => false,
+        };
+        if !shown {
+            return;
+        }
+
+        // Seule la première ligne tient dans le fil de messages
+        if let Some(line) = event.describe().as_deref().and_then(|text| text.lines().next()) {
+            if self.messages.len() == FEED_CAPACITY {
+                self.messages.pop_front();
+            }
+            self.messages.push_back((line.to_string(), FEED_DURATION));
+        }
+    }
+}
+
+// Bilan de la partie, affiché sur l'écran de fin
+#[derive(Debug, Default)]
+pub struct Statistics {
+    pub enemies_killed: u32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub coins_collected: u32,
+    pub items_collected: u32,
+    pub potions_used: u32,
+    pub purchases: u32,
+}
+
+impl EventSubscriber for Statistics {
+    fn handle(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::GameStarted { .. } => *self = Statistics::default(),
+            GameEvent::ItemCollected(ItemType::Coin) => self.coins_collected += 1,
+            GameEvent::ItemCollected(_) | GameEvent::ArtifactCollected { .. } => self.items_collected += 1,
+            GameEvent::Healed { .. } => self.potions_used += 1,
+            GameEvent::Purchased { .. } => self.purchases += 1,
+            GameEvent::DamageDealt { amount, .. } => self.damage_dealt += amount,
+            GameEvent::EnemyKilled(_) | GameEvent::BossDefeated => self.enemies_killed += 1,
+            GameEvent::PlayerDamaged { amount, .. } => self.damage_taken += amount,
+            _ => {}
+        }
+    }
+}
+
+// Associe un bruitage aux événements marquants. Les sons attendent la fin de la frame
+// pour être joués avec le contexte ggez ; sans la fonctionnalité `audio`, ils sont oubliés
+#[derive(Default)]
+pub struct AudioCues {
+    pending: Vec<SoundCue>,
+    // Chargés à la première utilisation ; None si le fichier manque
+    #[cfg(feature = "audio")]
+    sources: HashMap<SoundCue, Option<ggez::audio::Source>>,
+}
+
+impl AudioCues {
+    #[cfg(feature = "audio")]
+    pub fn play(&mut self, ctx: &mut Context) {
+        use ggez::audio::{SoundSource, Source};
+
+        for cue in self.pending.drain(..) {
+            let source = self.sources.entry(cue).or_insert_with(|| match Source::new(ctx, cue.path()) {
+                Ok(source) => Some(source),
+                Err(e) => {
+                    println!(" Son {} indisponible: {}", cue.path(), e);
+                    None
+                }
+            });
+            if let Some(source) = source {
+                if let Err(e) = source.play_detached(ctx) {
+                    println!(" Lecture de {} impossible: {}", cue.path(), e);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "audio"))]
+    pub fn play(&mut self, _ctx: &mut Context) {
+        self.pending.clear();
+    }
+}
+
+impl EventSubscriber for AudioCues {
+    fn handle(&mut self, event: &GameEvent) {
+        let cue = match event {
+            GameEvent::ItemCollected(ItemType::Coin) => SoundCue::Coin,
+            GameEvent::ItemCollected(_) | GameEvent::ArtifactCollected { .. } => SoundCue::Pickup,
+            GameEvent::Healed { .. } => SoundCue::Heal,
+            GameEvent::DamageDealt { .. } => SoundCue::Hit,
+            GameEvent::PlayerDamaged { .. } => SoundCue::Hurt,
+            GameEvent::BombExploded { .. } => SoundCue::Explosion,
+            GameEvent::Purchased { .. } => SoundCue::Purchase,
+            GameEvent::LevelUp { .. } => SoundCue::LevelUp,
+            GameEvent::BossSpawned | GameEvent::BossPhaseChanged(_) => SoundCue::BossRoar,
+            GameEvent::Victory { .. } => SoundCue::Victory,
+            GameEvent::PlayerDied => SoundCue::GameOver,
+            _ => return,
+        };
+        // Plusieurs coups dans la même frame ne font qu'un seul son
+        if !self.pending.contains(&cue) {
+            self.pending.push(cue);
+        }
+    }
+}
@@ -1,373 +1,346 @@
 use ggez::{Context, GameResult, event, graphics, input::keyboard::{KeyCode, KeyInput}};
-use ggez::graphics::Canvas;
+use ggez::graphics::{Canvas, DrawParam};
 use ggez::mint::Point2;
-use rand::Rng;
-
-use crate::player::{Player, Direction};
-use crate::enemy::{Enemy, EnemyType};
-use crate::items::{Item, ItemType};
-use crate::map::Map;
-use crate::ui::{UI, GameState};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::animation::{Animator, Clip};
+use crate::assets::{Assets, ImageId};
+use crate::camera::Camera;
+use crate::enemy::EnemyType;
+use crate::events::{AudioCues, ConsoleLogger, EventSubscriber, MessageFeed, Statistics};
+use crate::player::{Direction, StatChoice};
+use crate::replay::{Playback, Recorder, Replay};
+use crate::save::{self, SAVE_PATH};
+use crate::map::{Map, MapRenderer, TILE_SIZE};
+use crate::mapgen;
+use crate::tiled::{self, MAP_PATH};
+use crate::ui::UI;
+use crate::world::{Command, GameState, World};
+
+// Taille des cartes procédurales, bien plus grande que la fenêtre
+const MAP_WIDTH: f32 = 2560.0;
+const MAP_HEIGHT: f32 = 1920.0;
+const UPDATES_PER_SECOND: u32 = 60;
+// Marge autour de l'écran pour ne pas couper les sprites qui dépassent
+const CULL_MARGIN: f32 = 64.0;
+
+#[derive(Debug, Default)]
+pub struct LaunchOptions {
+    pub seed: Option<u64>,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub generate_map: bool,
+}
 
 pub struct Game {
-    player: Player,
-    enemies: Vec<Enemy>,
-    items: Vec<Item>,
-    map: Map,
-    game_state: GameState,
-    spawn_timer: f32,
-    quest_items_collected: u32,
-    total_quest_items: u32,
-    boss_spawned: bool,
-    game_time: f32,
+    world: World,
+    camera: Camera,
+    assets: Assets,
+    map_renderer: MapRenderer,
+    seed_override: Option<u64>,
+    recorder: Option<Recorder>,
+    playback: Option<Playback>,
+    generate_map: bool,
+    show_inventory: bool,
+    shop_open: bool,
+    // Horloge d'affichage, qui avance même quand la simulation est figée
+    clock: f32,
+    player_animator: Animator,
+    enemy_visuals: HashMap<u32, EnemyVisual>,
+    corpses: Vec<Corpse>,
+    console: ConsoleLogger,
+    feed: MessageFeed,
+    statistics: Statistics,
+    audio: AudioCues,
+}
+
+// Ce qu'il faut retenir d'un ennemi pour jouer son animation de mort après sa disparition
+struct EnemyVisual {
+    animator: Animator,
+    position: Point2<f32>,
+    enemy_type: EnemyType,
+}
+
+struct Corpse {
+    visual: EnemyVisual,
+}
+
+impl Corpse {
+    // Le cadavre s'efface au fil de l'animation de mort
+    fn draw(&self, canvas: &mut Canvas, assets: &Assets, clock: f32) {
+        let Some(sheet) = assets.enemy() else {
+            return;
+        };
+        let visual = &self.visual;
+        let mut tint = visual.enemy_type.sprite_tint();
+        tint.a = 1.0 - visual.animator.progress(clock);
+        sheet.draw(
+            canvas,
+            visual.animator.frame(clock),
+            DrawParam::default().dest(visual.position).color(tint),
+            visual.enemy_type.sprite_scale(),
+        );
+    }
 }
 
 impl Game {
-    pub fn new(ctx: &mut Context) -> GameResult<Self> {
-        let map_width = 800.0;
-        let map_height = 600.0;
-
-        let mut game = Game {
-            player: Player::new(ctx, 400.0, 300.0)?,
-            enemies: Vec::new(),
-            items: Vec::new(),
-            map: Map::new(ctx, map_width, map_height)?,
-            game_state: GameState::Playing,
-            spawn_timer: 2.0,
-            quest_items_collected: 0,
-            total_quest_items: 5, 
-            boss_spawned: false,
-            game_time: 0.0,
+    pub fn new(ctx: &mut Context, options: LaunchOptions) -> GameResult<Self> {
+        let playback = match &options.replay {
+            Some(path) => {
+                let replay = Replay::load(path).map_err(|e| {
+                    ggez::GameError::CustomError(format!("replay {} illisible: {}", path.display(), e))
+                })?;
+                println!(" Lecture du replay {} ({} entrées)", path.display(), replay.inputs.len());
+                Some(Playback::new(replay))
+            }
+            None => None,
         };
 
-        game.spawn_initial_items(ctx)?;
-        game.spawn_quest_items(ctx)?;
-        
-        println!(" Nouvelle partie lancée !");
-        println!(" Objectif: Collecter {} artefacts pour affronter le Dragon !", game.total_quest_items);
-        
-        Ok(game)
+        let seed = match &playback {
+            Some(playback) => playback.seed(),
+            None => choose_seed(options.seed),
+        };
+        // Un replay impose le type de carte avec lequel il a été enregistré
+        let generate_map = match &playback {
+            Some(playback) => playback.generated_map(),
+            None => options.generate_map,
+        };
+        let recorder = options.record.map(|path| Recorder::new(seed, generate_map, path));
+
+        let world = World::new(load_map(generate_map, seed), seed);
+        let (screen_width, screen_height) = ctx.gfx.drawable_size();
+        let mut camera = Camera::new(screen_width, screen_height);
+        camera.snap_to(world.player.position, world.map.width, world.map.height);
+
+        Ok(Game {
+            world,
+            camera,
+            assets: Assets::load(ctx),
+            map_renderer: MapRenderer::new(),
+            seed_override: options.seed,
+            recorder,
+            playback,
+            generate_map,
+            show_inventory: false,
+            shop_open: false,
+            clock: 0.0,
+            player_animator: Animator::new(Clip::Idle, 0.0),
+            enemy_visuals: HashMap::new(),
+            corpses: Vec::new(),
+            console: ConsoleLogger,
+            feed: MessageFeed::default(),
+            statistics: Statistics::default(),
+            audio: AudioCues::default(),
+        })
     }
 
-    fn spawn_initial_items(&mut self, ctx: &mut Context) -> GameResult<()> {
-        let mut rng = rand::thread_rng();
-        
-        for _ in 0..15 {
-            let item_type = match rng.gen_range(0..3) {
-                0 => ItemType::Coin,
-                1 => ItemType::Potion,
-                _ => ItemType::WeaponUpgrade,
-            };
-            
-            let mut position;
-            let mut attempts = 0;
-            
-            loop {
-                position = Point2 {
-                    x: rng.gen_range(100.0..700.0),
-                    y: rng.gen_range(100.0..500.0),
-                };
-                
-                if self.map.is_position_valid(position, 10.0) && attempts < 50 {
-                    break;
-                }
-                attempts += 1;
-                
-                if attempts >= 50 {
-                    position = Point2 { x: 400.0, y: 300.0 };
-                    break;
-                }
-            }
-            
-            self.items.push(Item::new(ctx, position.x, position.y, item_type));
+    fn sync_animations(&mut self) {
+        let clock = self.clock;
+        self.player_animator.play(self.world.player.animation_clip(), clock);
+
+        for enemy in self.world.enemies.iter().filter(|enemy| enemy.is_alive) {
+            let visual = self.enemy_visuals.entry(enemy.id).or_insert_with(|| EnemyVisual {
+                animator: Animator::new(Clip::Idle, clock),
+                position: enemy.position,
+                enemy_type: enemy.enemy_type,
+            });
+            visual.animator.play(enemy.animation_clip(), clock);
+            visual.position = enemy.position;
         }
-        
-        Ok(())
-    }
 
-    fn spawn_quest_items(&mut self, ctx: &mut Context) -> GameResult<()> {
-        let mut rng = rand::thread_rng();
-        
-        for i in 0..self.total_quest_items {
-            let mut position;
-            let mut attempts = 0;
-            
-            loop {
-                position = Point2 {
-                    x: rng.gen_range(100.0..700.0),
-                    y: rng.gen_range(100.0..500.0),
-                };
-                
-                if self.map.is_position_valid(position, 15.0) && attempts < 50 {
-                    break;
-                }
-                attempts += 1;
-                
-                if attempts >= 50 {
-                
-                    let angle = (i as f32 / self.total_quest_items as f32) * 2.0 * std::f32::consts::PI;
-                    position = Point2 {
-                        x: 400.0 + angle.cos() * 200.0,
-                        y: 300.0 + angle.sin() * 150.0,
-                    };
-                    break;
-                }
+        // Un ennemi disparu du monde laisse un cadavre le temps de son animation de mort
+        let alive: Vec<u32> = self.world.enemies.iter().filter(|enemy| enemy.is_alive).map(|enemy| enemy.id).collect();
+        let gone: Vec<u32> = self.enemy_visuals.keys().copied().filter(|id| !alive.contains(id)).collect();
+        for id in gone {
+            if let Some(mut visual) = self.enemy_visuals.remove(&id) {
+                visual.animator.play(Clip::Death, clock);
+                self.corpses.push(Corpse { visual });
             }
-            
-         
-            self.items.push(Item::new_quest(ctx, position.x, position.y));
         }
-        
-        println!(" {} artefacts cachés sur la carte !", self.total_quest_items);
-        Ok(())
+        self.corpses.retain(|corpse| !corpse.visual.animator.is_finished(clock));
     }
 
-    fn spawn_enemy(&mut self, ctx: &mut Context) -> GameResult<()> {
-        let mut rng = rand::thread_rng();
-        
-        let enemy_type = match rng.gen_range(0..10) {
-            0..=5 => EnemyType::Goblin,
-            6..=8 => EnemyType::Orc,
-            _ => EnemyType::Dragon,
-        };
-
-        let mut position;
-        let mut attempts = 0;
-        
-        loop {
-         
-            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
-            let distance = rng.gen_range(150.0..300.0);
-            position = Point2 {
-                x: self.player.position.x + angle.cos() * distance,
-                y: self.player.position.y + angle.sin() * distance,
-            };
-            
-            if self.map.is_position_valid(position, 12.0) &&
-               position.x >= 50.0 && position.x <= self.map.width - 50.0 &&
-               position.y >= 50.0 && position.y <= self.map.height - 50.0 &&
-               attempts < 30 {
-                break;
-            }
-            attempts += 1;
-            
-            if attempts >= 30 {
-               
-                position = Point2 {
-                    x: rng.gen_range(100.0..700.0),
-                    y: rng.gen_range(100.0..500.0),
-                };
-                break;
+    // Chaque événement de la simulation est transmis à tous les abonnés, dans l'ordre
+    fn dispatch_events(&mut self) {
+        let events = self.world.drain_events();
+        let mut subscribers: [&mut dyn EventSubscriber; 4] =
+            [&mut self.console, &mut self.feed, &mut self.statistics, &mut self.audio];
+        for event in &events {
+            for subscriber in subscribers.iter_mut() {
+                subscriber.handle(event);
             }
         }
-
-        self.enemies.push(Enemy::new(ctx, position.x, position.y, enemy_type));
-        
-        if enemy_type == EnemyType::Dragon && !self.boss_spawned {
-            println!(" Un dragon sauvage apparaît !");
-        }
-        
-        Ok(())
     }
 
-    fn spawn_boss(&mut self, ctx: &mut Context) -> GameResult<()> {
-        if !self.boss_spawned {
-         
-            let boss_position = Point2 { x: 400.0, y: 100.0 };
-            let boss = Enemy::new(ctx, boss_position.x, boss_position.y, EnemyType::Dragon);
-            self.enemies.push(boss);
-            self.boss_spawned = true;
-            println!(" LE BOSS DRAGON APPARAÎT !");
-            println!(" Affrontez-le pour gagner la partie !");
-        }
-        Ok(())
+    // Nouvelle partie ou partie chargée : tout ce qui dépend de l'ancien monde est à refaire
+    fn world_replaced(&mut self) {
+        self.camera.snap_to(self.world.player.position, self.world.map.width, self.world.map.height);
+        self.map_renderer.invalidate();
+        self.player_animator = Animator::new(Clip::Idle, self.clock);
+        self.enemy_visuals.clear();
+        self.corpses.clear();
+        // Une partie chargée n'émet pas GameStarted : le bilan et les messages repartent de zéro
+        self.feed = MessageFeed::default();
+        self.statistics = Statistics::default();
     }
 
-    fn check_collisions(&mut self) {
- 
-        let mut new_quest_items = 0;
-        
-        for item in &mut self.items {
-            if !item.is_collected && item.is_colliding_with_player(self.player.position) {
-                match item.collect() {
-                    ItemType::Coin => {
-                        self.player.add_coin();
-                        println!("Pièce collectée ! Total: {}", self.player.coins);
-                    }
-                    ItemType::Potion => {
-                        let old_health = self.player.health;
-                        self.player.heal(25);
-                        println!(" Potion utilisée ! Nour: {} → {}", old_health, self.player.health);
-                    }
-                    ItemType::WeaponUpgrade => {
-                        let old_level = self.player.weapon_level;
-                        self.player.upgrade_weapon();
-                        println!("  Arme améliorée ! Niveau: {} → {}", old_level, self.player.weapon_level);
-                    }
-                    ItemType::QuestArtifact => {
-                        self.quest_items_collected += 1;
-                        new_quest_items += 1;
-                        let remaining = self.total_quest_items - self.quest_items_collected;
-                        println!(" ARTEFACT COLLECTÉ ! ({}/{}) - Plus que {} restant(s)", 
-                                self.quest_items_collected, self.total_quest_items, remaining);
-                    }
-                }
-            }
+    fn send(&mut self, command: Command) {
+        if self.playback.is_some() {
+            return;
         }
-
-        if new_quest_items > 0 && self.quest_items_collected == self.total_quest_items {
-            println!(" TOUS LES ARTEFACTS COLLECTÉS !");
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(self.world.frame, command);
         }
+        self.world.apply(command);
+    }
 
-        self.items.retain(|item| !item.is_collected);
-
-      
-        for enemy in &mut self.enemies {
-            if enemy.is_alive && enemy.is_in_attack_range(self.player.position) && enemy.can_attack() {
-                let damage = enemy.attack();
-                let old_health = self.player.health;
-                self.player.take_damage(damage);
-                
-                let enemy_name = match enemy.enemy_type {
-                    EnemyType::Goblin => "Goblin",
-                    EnemyType::Orc => "Orc",
-                    EnemyType::Dragon => "Dragon",
-                };
-                
-                println!(" {} vous attaque ! -{} PV ({} → {})", 
-                        enemy_name, damage, old_health, self.player.health);
-                
-                if !self.player.is_alive() {
-                    self.game_state = GameState::GameOver;
-                    println!(" GAME OVER! Le héros est tombé au combat...");
-                }
+    fn restart(&mut self) {
+        let seed = match &mut self.playback {
+            Some(playback) => {
+                playback.rewind();
+                playback.seed()
             }
+            None => choose_seed(self.seed_override),
+        };
+        if let Some(recorder) = &mut self.recorder {
+            recorder.restart(seed);
         }
+        self.world = World::new(load_map(self.generate_map, seed), seed);
+        self.world_replaced();
+    }
 
-        if self.player.is_attacking {
-            let mut enemies_hit = 0;
-            
-            for enemy in &mut self.enemies {
-                if enemy.is_alive {
-                    let dx = enemy.position.x - self.player.position.x;
-                    let dy = enemy.position.y - self.player.position.y;
-                    let distance = (dx * dx + dy * dy).sqrt();
-
-                    if distance < 50.0 { 
-                        let damage = self.player.get_attack_damage();
-                        let old_health = enemy.health;
-                        enemy.take_damage(damage);
-                        enemies_hit += 1;
-                        
-                        let enemy_name = match enemy.enemy_type {
-                            EnemyType::Goblin => "Goblin",
-                            EnemyType::Orc => "Orc",
-                            EnemyType::Dragon => "Dragon",
-                        };
-                        
-                        if !enemy.is_alive {
-                            println!(" {} vaincu ! -{} nour", enemy_name, damage);
-                        } else {
-                            println!(" {} touché ! -{} nour ({} → {})", 
-                                    enemy_name, damage, old_health, enemy.health);
-                        }
-                    }
-                }
-            }
-            
-            if enemies_hit > 0 {
-                println!("Attaque réussie ! {} ennemi(s) touché(s)", enemies_hit);
+    fn near_merchant(&self) -> bool {
+        self.world.merchant.is_in_reach(&self.world.player.collider())
+    }
+
+    // Les touches 1 à 4 servent d'abord au choix d'amélioration, puis à la boutique,
+    // sinon elles utilisent la barre d'objets
+    fn slot_key(&mut self, slot: usize) {
+        if self.world.player.pending_level_ups > 0 {
+            if let Some(&choice) = StatChoice::ALL.get(slot) {
+                self.send(Command::ChooseStat(choice));
             }
+        } else if self.shop_open {
+            self.send(Command::Buy(slot));
+        } else {
+            self.send(Command::UseItem(slot));
         }
+    }
+}
 
-    
-        let enemies_before = self.enemies.len();
-        self.enemies.retain(|enemy| enemy.is_alive);
-        let enemies_after = self.enemies.len();
-        
-        if enemies_before != enemies_after {
-            println!(" {} ennemi(s) nettoyé(s)", enemies_before - enemies_after);
-        }
+// Carte générée à partir de la graine si demandé, sinon la carte dessinée sous Tiled,
+// et la carte procédurale d'origine en dernier recours
+fn load_map(generate_map: bool, seed: u64) -> Map {
+    if generate_map {
+        return mapgen::generate((MAP_WIDTH / TILE_SIZE) as usize, (MAP_HEIGHT / TILE_SIZE) as usize, seed);
     }
 
-    fn check_quest_progress(&mut self, ctx: &mut Context) -> GameResult<()> {
-     
-        if self.quest_items_collected >= self.total_quest_items && !self.boss_spawned {
-            println!(" Tous les artefacts collectés ! Le boss final arrive...");
-            self.spawn_boss(ctx)?;
-        }
-    
-        if self.boss_spawned {
-            let boss_alive = self.enemies.iter()
-                .any(|e| e.is_alive && e.enemy_type == EnemyType::Dragon);
-            
-            if !boss_alive {
-                self.game_state = GameState::Victory;
-                println!("VICTOIRE ! Vous avez sauvé le royaume !");
-                println!(" Temps total: {:.1} secondes", self.game_time);
-                println!(" Pièces collectées: {}", self.player.coins);
-            }
+    match tiled::load_map(MAP_PATH) {
+        Ok(map) => map,
+        Err(e) => {
+            println!(" Carte {} indisponible ({}), carte procédurale utilisée", MAP_PATH, e);
+            Map::new(MAP_WIDTH, MAP_HEIGHT)
         }
-        
-        Ok(())
     }
 }
 
+fn choose_seed(seed_override: Option<u64>) -> u64 {
+    seed_override.unwrap_or_else(rand::random)
+}
+
 impl event::EventHandler for Game {
     fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
-        if self.game_state != GameState::Playing {
-            return Ok(());
-        }
+        // Pas de simulation fixe (1/60 s) pour que les replays restent reproductibles
+        let dt = 1.0 / UPDATES_PER_SECOND as f32;
+
+        while ctx.time.check_update_time(UPDATES_PER_SECOND) {
+            if let Some(playback) = &mut self.playback {
+                while let Some(command) = playback.next_command(self.world.frame) {
+                    self.world.apply(command);
+                }
+            }
 
-        let dt = ctx.time.delta().as_secs_f32();
-        self.game_time += dt;
-        self.player.update(dt, self.map.width, self.map.height);
+            let was_playing = self.world.game_state == GameState::Playing;
+            self.world.update(dt);
 
-        for enemy in &mut self.enemies {
-            enemy.update(dt, self.player.position);
+            if was_playing && self.world.game_state != GameState::Playing {
+                if let Some(recorder) = &self.recorder {
+                    recorder.flush();
+                }
+            }
         }
 
-        self.spawn_timer -= dt;
-        if self.spawn_timer <= 0.0 && self.enemies.len() < 8 {
-            self.spawn_enemy(ctx)?;
-            self.spawn_timer = 3.0;
+        self.dispatch_events();
+        self.audio.play(ctx);
+        self.feed.update(ctx.time.delta().as_secs_f32());
+
+        self.clock += ctx.time.delta().as_secs_f32();
+        self.sync_animations();
+
+        let (screen_width, screen_height) = ctx.gfx.drawable_size();
+        self.camera.resize(screen_width, screen_height);
+        self.camera.follow(
+            self.world.player.position,
+            self.world.map.width,
+            self.world.map.height,
+            ctx.time.delta().as_secs_f32(),
+        );
+
+        // La boutique se ferme dès que le héros s'éloigne du marchand
+        if self.shop_open && !self.near_merchant() {
+            self.shop_open = false;
         }
-
-        self.check_collisions();
-
-     
-        self.check_quest_progress(ctx)?;
-
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
         let mut canvas = Canvas::from_frame(ctx, graphics::Color::BLACK);
 
-        self.map.draw(ctx, &mut canvas)?;
+        // Le monde est dessiné dans le repère de la caméra, l'interface dans celui de l'écran
+        let view = self.camera.view_rect();
+        canvas.set_screen_coordinates(view);
 
-        for item in &self.items {
-            item.draw(ctx, &mut canvas)?;
+        self.map_renderer.draw(ctx, &mut canvas, &self.world.map, self.assets.image(ImageId::Tileset), view)?;
+
+        self.world.merchant.draw(ctx, &mut canvas)?;
+
+        let view_center = Point2 { x: view.x + view.w / 2.0, y: view.y + view.h / 2.0 };
+        let (cull_width, cull_height) = (view.w + CULL_MARGIN * 2.0, view.h + CULL_MARGIN * 2.0);
+
+        for item in self.world.items_in(view_center, cull_width, cull_height) {
+            item.draw(ctx, &mut canvas, self.assets.item(item.item_type))?;
         }
 
-        for enemy in &self.enemies {
-            enemy.draw(ctx, &mut canvas)?;
+        for corpse in self.corpses.iter().filter(|corpse| self.camera.is_visible(corpse.visual.position, CULL_MARGIN)) {
+            corpse.draw(&mut canvas, &self.assets, self.clock);
         }
 
-        self.player.draw(ctx, &mut canvas)?;
-
-     
-        UI::draw(
-            ctx,
-            &mut canvas,
-            self.player.health,
-            self.player.max_health,
-            self.player.coins,
-            self.player.weapon_level,
-            (self.quest_items_collected, self.total_quest_items),
-            self.game_time,
-            &self.game_state,
-        )?;
+        for enemy in self.world.enemies_in(view_center, cull_width, cull_height) {
+            let frame = match self.enemy_visuals.get(&enemy.id) {
+                Some(visual) => visual.animator.frame(self.clock),
+                None => Animator::new(Clip::Idle, self.clock).frame(self.clock),
+            };
+            enemy.draw(ctx, &mut canvas, self.assets.enemy().as_ref(), frame)?;
+        }
+
+        for projectile in self.world.projectiles.iter().filter(|p| self.camera.is_visible(p.position, CULL_MARGIN)) {
+            projectile.draw(ctx, &mut canvas)?;
+        }
+
+        if let Some(boss) = &self.world.boss {
+            boss.draw(ctx, &mut canvas, self.assets.boss(), self.world.game_time)?;
+        }
+
+        let player_frame = self.player_animator.frame(self.clock);
+        self.world.player.draw(ctx, &mut canvas, self.assets.player().as_ref(), player_frame)?;
+
+        canvas.set_screen_coordinates(graphics::Rect::new(0.0, 0.0, view.w, view.h));
+        UI::draw(ctx, &mut canvas, &self.world, &self.camera, self.show_inventory, self.shop_open)?;
+        UI::draw_messages(ctx, &mut canvas, &self.feed);
+        if self.world.game_state != GameState::Playing {
+            UI::draw_statistics(&mut canvas, &self.statistics);
+        }
 
         canvas.finish(ctx)?;
         Ok(())
@@ -377,23 +350,58 @@ impl event::EventHandler for Game {
         if let Some(keycode) = input.keycode {
             match keycode {
                 KeyCode::Z | KeyCode::Up => {
-                    self.player.move_direction(Direction::Up);
+                    self.send(Command::Move(Direction::Up));
                 }
                 KeyCode::S | KeyCode::Down => {
-                    self.player.move_direction(Direction::Down);
+                    self.send(Command::Move(Direction::Down));
                 }
                 KeyCode::Q | KeyCode::Left => {
-                    self.player.move_direction(Direction::Left);
+                    self.send(Command::Move(Direction::Left));
                 }
                 KeyCode::D | KeyCode::Right => {
-                    self.player.move_direction(Direction::Right);
+                    self.send(Command::Move(Direction::Right));
                 }
                 KeyCode::Space => {
-                    self.player.attack();
+                    self.send(Command::Attack);
                 }
-                KeyCode::R if self.game_state != GameState::Playing => {
+                KeyCode::Key1 => self.slot_key(0),
+                KeyCode::Key2 => self.slot_key(1),
+                KeyCode::Key3 => self.slot_key(2),
+                KeyCode::Key4 => self.slot_key(3),
+                KeyCode::F if self.shop_open || self.near_merchant() => {
+                    self.shop_open = !self.shop_open;
+                }
+                KeyCode::I | KeyCode::Tab => {
+                    self.show_inventory = !self.show_inventory;
+                }
+                KeyCode::R if self.world.game_state != GameState::Playing => {
                     println!(" Redémarrage de la partie...");
-                    *self = Game::new(ctx)?;
+                    self.restart();
+                }
+                KeyCode::F5 if self.world.game_state == GameState::Playing => {
+                    match save::save_world(&self.world, SAVE_PATH) {
+                        Ok(()) => println!(" Partie sauvegardée dans {}", SAVE_PATH),
+                        Err(e) => println!(" Échec de la sauvegarde: {}", e),
+                    }
+                }
+                KeyCode::F9 if self.playback.is_none() => {
+                    match save::load_world(SAVE_PATH) {
+                        Ok(world) => {
+                            self.world = world;
+                            self.world_replaced();
+                            println!(" Partie chargée depuis {}", SAVE_PATH);
+                            if self.recorder.take().is_some() {
+                                println!(" Enregistrement du replay interrompu par le chargement");
+                            }
+                        }
+                        Err(e) => println!(" Échec du chargement: {}", e),
+                    }
+                }
+                // Pendant le développement, F6 relit les images modifiées sur le disque
+                KeyCode::F6 if cfg!(debug_assertions) => {
+                    self.assets.reload(ctx);
+                    self.map_renderer.invalidate();
+                    println!(" Images rechargées");
                 }
                 KeyCode::Escape => {
                     println!("Au revoir !");
@@ -406,15 +414,19 @@ impl event::EventHandler for Game {
     }
 
     fn key_up_event(&mut self, _ctx: &mut Context, input: KeyInput) -> GameResult<()> {
-        if let Some(keycode) = input.keycode {
-            match keycode {
-                KeyCode::Z | KeyCode::S | KeyCode::Q | KeyCode::D |
-                KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
-                    self.player.stop_movement();
-                }
-                _ => {}
-            }
+        if let Some(
+            KeyCode::Z | KeyCode::S | KeyCode::Q | KeyCode::D |
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right
+        ) = input.keycode {
+            self.send(Command::StopMovement);
         }
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn quit_event(&mut self, _ctx: &mut Context) -> GameResult<bool> {
+        if let Some(recorder) = &self.recorder {
+            recorder.flush();
+        }
+        Ok(false)
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+use crate::items::ItemType;
+
+pub const INVENTORY_CAPACITY: usize = 8;
+// Les premiers emplacements forment la barre d'accès rapide (touches 1 à 4)
+pub const HOTBAR_SIZE: usize = 4;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ItemStack {
+    pub item_type: ItemType,
+    pub count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Inventory {
+            slots: vec![None; INVENTORY_CAPACITY],
+        }
+    }
+
+    // Complète d'abord une pile existante, sinon prend le premier emplacement libre.
+    // Renvoie faux si l'inventaire est plein.
+    pub fn add(&mut self, item_type: ItemType) -> bool {
+        let max_stack = item_type.max_stack();
+        if let Some(stack) = self.slots.iter_mut()
+            .flatten()
+            .find(|stack| stack.item_type == item_type && stack.count < max_stack) {
+            stack.count += 1;
+            return true;
+        }
+
+        match self.slots.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(ItemStack { item_type, count: 1 });
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_add(&self, item_type: ItemType) -> bool {
+        self.slots.iter().any(|slot| match slot {
+            Some(stack) => stack.item_type == item_type && stack.count < item_type.max_stack(),
+            None => true,
+        })
+    }
+
+    pub fn slot(&self, index: usize) -> Option<ItemStack> {
+        self.slots.get(index).copied().flatten()
+    }
+
+    // Un emplacement de la barre qui se vide reprend la première pile du sac,
+    // sinon les objets rangés au-delà de la barre ne pourraient jamais servir
+    pub fn remove_one(&mut self, index: usize) {
+        let Some(slot) = self.slots.get_mut(index) else {
+            return;
+        };
+        if let Some(stack) = slot {
+            stack.count -= 1;
+            if stack.count == 0 {
+                *slot = None;
+            }
+        }
+
+        if index < HOTBAR_SIZE && self.slots[index].is_none() {
+            if let Some(offset) = self.slots[HOTBAR_SIZE..].iter().position(Option::is_some) {
+                self.slots[index] = self.slots[HOTBAR_SIZE + offset].take();
+            }
+        }
+    }
+
+    pub fn used_slots(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_stacks_up_to_the_limit() {
+        let mut inventory = Inventory::new();
+        for _ in 0..7 {
+            assert!(inventory.add(ItemType::Potion));
+        }
+        assert_eq!(inventory.slot(0).map(|stack| stack.count), Some(5));
+        assert_eq!(inventory.slot(1).map(|stack| stack.count), Some(2));
+        assert_eq!(inventory.used_slots(), 2);
+    }
+
+    #[test]
+    fn add_fails_when_full() {
+        let mut inventory = Inventory::new();
+        for _ in 0..INVENTORY_CAPACITY * 5 {
+            assert!(inventory.add(ItemType::Bomb));
+        }
+        assert!(!inventory.add(ItemType::Bomb));
+        assert!(!inventory.add(ItemType::Potion));
+        assert_eq!(inventory.used_slots(), INVENTORY_CAPACITY);
+    }
+
+    #[test]
+    fn remove_one_empties_the_slot_with_its_last_item() {
+        let mut inventory = Inventory::new();
+        inventory.add(ItemType::Potion);
+        inventory.add(ItemType::Potion);
+
+        inventory.remove_one(0);
+        assert_eq!(inventory.slot(0).map(|stack| stack.count), Some(1));
+        inventory.remove_one(0);
+        assert!(inventory.slot(0).is_none());
+    }
+
+    #[test]
+    fn emptied_hotbar_slot_takes_a_stack_from_the_bag() {
+        let mut inventory = Inventory::new();
+        for _ in 0..HOTBAR_SIZE * 5 {
+            inventory.add(ItemType::Potion);
+        }
+        inventory.add(ItemType::Bomb);
+
+        for _ in 0..5 {
+            inventory.remove_one(0);
+        }
+        assert_eq!(inventory.slot(0).map(|stack| stack.item_type), Some(ItemType::Bomb));
+        assert!(inventory.slot(HOTBAR_SIZE).is_none());
+    }
+}
@@ -1,50 +1,71 @@
 use ggez::{Context, GameResult, graphics};
 use ggez::graphics::{Canvas, DrawParam, Color, Image, Mesh};
 use ggez::mint::Point2;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+use crate::collision::Collider;
+use crate::weapon::WeaponKind;
+
+pub const ITEM_RADIUS: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ItemType {
     Coin,
     Potion,
-    WeaponUpgrade,
+    Weapon(WeaponKind),
     QuestArtifact,
+    Key,
+    Bomb,
+}
+
+impl ItemType {
+    // Les objets transportés vont dans l'inventaire, les autres s'appliquent au ramassage
+    pub fn is_carried(self) -> bool {
+        matches!(self, ItemType::Potion | ItemType::Key | ItemType::Bomb | ItemType::Weapon(_))
+    }
+
+    pub fn max_stack(self) -> u32 {
+        match self {
+            ItemType::Potion => 5,
+            ItemType::Bomb => 5,
+            ItemType::Key => 3,
+            _ => 1,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ItemType::Coin => "Pièce",
+            ItemType::Potion => "Potion",
+            ItemType::Weapon(weapon) => weapon.definition().name,
+            ItemType::QuestArtifact => "Artefact",
+            ItemType::Key => "Clé",
+            ItemType::Bomb => "Bombe",
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Item {
     pub position: Point2<f32>,
     pub item_type: ItemType,
     pub is_collected: bool,
-    sprite: Option<Image>,
 }
 
 impl Item {
-    pub fn new(ctx: &mut Context, x: f32, y: f32, item_type: ItemType) -> Self {
-        let sprite_path = match item_type {
-            ItemType::Coin => "/coin.png",
-            ItemType::Potion => "/potion.png",
-            ItemType::WeaponUpgrade => "/weapon.png",
-            ItemType::QuestArtifact => "/artifact.png",
-        };
-        
-        let sprite = Image::from_path(ctx, sprite_path).ok();
-
+    pub fn new(x: f32, y: f32, item_type: ItemType) -> Self {
         Item {
             position: Point2 { x, y },
             item_type,
             is_collected: false,
-            sprite,
         }
     }
 
-    pub fn new_quest(ctx: &mut Context, x: f32, y: f32) -> Self {
-        let sprite = Image::from_path(ctx, "/artifact.png").ok();
-
+    pub fn new_quest(x: f32, y: f32) -> Self {
         Item {
             position: Point2 { x, y },
             item_type: ItemType::QuestArtifact,
             is_collected: false,
-            sprite,
         }
     }
 
@@ -54,25 +75,24 @@ impl Item {
     }
 
 
-    pub fn is_colliding_with_player(&self, player_pos: Point2<f32>) -> bool {
+    pub fn collider(&self) -> Collider {
+        Collider::circle(self.position, ITEM_RADIUS)
+    }
+
+    pub fn is_colliding_with_player(&self, player: &Collider) -> bool {
         if self.is_collected {
             return false;
         }
 
-        let dx = player_pos.x - self.position.x;
-        let dy = player_pos.y - self.position.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-  
-        distance < 25.0
+        self.collider().intersects(player)
     }
 
-    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult<()> {
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, sprite: Option<&Image>) -> GameResult<()> {
         if self.is_collected {
             return Ok(());
         }
 
-        if let Some(sprite) = &self.sprite {
+        if let Some(sprite) = sprite {
             let time = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
@@ -83,7 +103,7 @@ impl Item {
                 ItemType::QuestArtifact => {
               
                     let pulse = 1.0 + (time * 4.0).sin() * 0.2;
-                    let glow = ((time * 3.0).sin() * 0.3 + 0.7) as f32;
+                    let glow = (time * 3.0).sin() * 0.3 + 0.7;
                     
                     canvas.draw(
                         sprite,
@@ -112,8 +132,10 @@ impl Item {
             let (color, size) = match self.item_type {
                 ItemType::Coin => (Color::YELLOW, 8.0),
                 ItemType::Potion => (Color::from_rgb(255, 0, 255), 10.0),
-                ItemType::WeaponUpgrade => (Color::from_rgb(200, 200, 0), 12.0),
+                ItemType::Weapon(_) => (Color::from_rgb(200, 200, 0), 12.0),
                 ItemType::QuestArtifact => (Color::from_rgb(255, 215, 0), 15.0), // Artefact doré
+                ItemType::Key => (Color::from_rgb(190, 190, 210), 8.0),
+                ItemType::Bomb => (Color::from_rgb(40, 40, 40), 10.0),
             };
 
             let shape = Mesh::new_circle(
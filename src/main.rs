@@ -5,11 +5,29 @@ mod enemy;
 mod items;
 mod map;
 mod ui;
+mod assets;
+mod world;
+mod save;
+mod replay;
+mod collision;
+mod pathfinding;
+mod boss;
+mod projectile;
+mod inventory;
+mod shop;
+mod weapon;
+mod tiled;
+mod mapgen;
+mod camera;
+mod animation;
+mod spatial;
+mod events;
 
 use ggez::{ContextBuilder, event, conf};
-use game::Game;
+use game::{Game, LaunchOptions};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let options = parse_args()?;
     let resource_dir = PathBuf::from("./resources");
 
     let window_setup = conf::WindowSetup::default()
@@ -24,11 +42,35 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .window_mode(window_mode)
         .build()?;
 
-    let game = Game::new(&mut ctx)?;
+    let game = Game::new(&mut ctx, options)?;
     
     println!(" Jeu lancé avec succès !");
     println!(" Mode WSL : Audio désactivé");
     println!(" Prêt à jouer !");
     
     event::run(ctx, event_loop, game);
-}
\ No newline at end of file
+}
+
+fn parse_args() -> Result<LaunchOptions, Box<dyn std::error::Error>> {
+    let mut options = LaunchOptions::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args.next().ok_or("--seed attend une valeur")?;
+                options.seed = Some(value.parse()?);
+            }
+            "--record" => {
+                let value = args.next().ok_or("--record attend un fichier")?;
+                options.record = Some(PathBuf::from(value));
+            }
+            "--generate" => options.generate_map = true,
+            "--replay" => {
+                let value = args.next().ok_or("--replay attend un fichier")?;
+                options.replay = Some(PathBuf::from(value));
+            }
+            _ => return Err(format!("argument inconnu: {}", arg).into()),
+        }
+    }
+    Ok(options)
+}
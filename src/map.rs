@@ -1,29 +1,83 @@
 use ggez::{Context, GameResult, graphics};
-use ggez::graphics::{Canvas, DrawParam, Color, Image, Mesh};
+use ggez::graphics::{Canvas, DrawParam, Color, Image, InstanceArray, Mesh, MeshBuilder, Rect};
 use ggez::mint::Point2;
+use serde::{Deserialize, Serialize};
 
+use crate::collision::Collider;
+use crate::spatial::SpatialHash;
+
+pub const TILE_SIZE: f32 = 64.0;
+
+#[derive(Serialize, Deserialize)]
 pub struct Map {
     pub width: f32,
     pub height: f32,
     tiles: Vec<Tile>,
     obstacles: Vec<Obstacle>,
-    tileset: Option<Image>,
+    // Reconstruit après un chargement (voir World::rebuild_caches)
+    #[serde(skip)]
+    obstacle_index: SpatialHash,
+    pub markers: MapMarkers,
+}
+
+// Points remarquables placés par les cartes dessinées à la main
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MapMarkers {
+    pub player_spawn: Option<Point2<f32>>,
+    pub merchant: Option<Point2<f32>>,
+    pub boss_arena: Option<Point2<f32>>,
+    pub enemy_spawns: Vec<Point2<f32>>,
+    pub artifacts: Vec<Point2<f32>>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct Tile {
     position: Point2<f32>,
     tile_type: TileType,
 }
 
-#[derive(Clone, PartialEq)]
-enum TileType {
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TileType {
     Grass,
     Path,
     Water,
     Stone,
 }
 
+impl TileType {
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            TileType::Grass | TileType::Stone => 1.0,
+            TileType::Path => 1.3,
+            TileType::Water => 0.4,
+        }
+    }
+
+    pub fn allows_spawn(self) -> bool {
+        self != TileType::Stone
+    }
+
+    // Colonne du terrain dans tileset.png, la même que dans les cartes Tiled
+    fn tileset_column(self) -> u32 {
+        match self {
+            TileType::Path => 0,
+            TileType::Grass => 1,
+            TileType::Water => 2,
+            TileType::Stone => 3,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            TileType::Grass => Color::from_rgb(100, 200, 100),
+            TileType::Path => Color::from_rgb(200, 180, 100),
+            TileType::Water => Color::from_rgb(100, 150, 255),
+            TileType::Stone => Color::from_rgb(150, 150, 150),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 struct Obstacle {
     position: Point2<f32>,
     width: f32,
@@ -31,21 +85,52 @@ struct Obstacle {
     obstacle_type: ObstacleType,
 }
 
-#[derive(PartialEq)]
-enum ObstacleType {
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ObstacleType {
     Tree,
     Rock,
     Bush,
 }
 
+impl Obstacle {
+    fn collider(&self) -> Collider {
+        match self.obstacle_type {
+            ObstacleType::Rock => Collider::aabb(self.position, self.width, self.height),
+            ObstacleType::Tree | ObstacleType::Bush => Collider::circle(self.position, self.width / 2.0),
+        }
+    }
+
+    fn add_to(&self, builder: &mut MeshBuilder) -> GameResult<()> {
+        match self.obstacle_type {
+            ObstacleType::Tree => {
+                builder.circle(graphics::DrawMode::fill(), self.position, self.width / 2.0, 0.1, Color::from_rgb(50, 120, 50))?;
+            }
+            ObstacleType::Bush => {
+                builder.circle(graphics::DrawMode::fill(), self.position, self.width / 2.0, 0.1, Color::from_rgb(80, 160, 80))?;
+            }
+            ObstacleType::Rock => {
+                builder.rectangle(
+                    graphics::DrawMode::fill(),
+                    Rect::new(
+                        self.position.x - self.width / 2.0,
+                        self.position.y - self.height / 2.0,
+                        self.width,
+                        self.height,
+                    ),
+                    Color::from_rgb(120, 120, 120),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Map {
-    pub fn new(ctx: &mut Context, width: f32, height: f32) -> GameResult<Self> {
-        let tileset = Image::from_path(ctx, "/tileset.png").ok();
-        
+    pub fn new(width: f32, height: f32) -> Self {
         let mut tiles = Vec::new();
         let mut obstacles = Vec::new();
         
-        let tile_size = 64.0;
+        let tile_size = TILE_SIZE;
         let cols = (width / tile_size).ceil() as usize;
         let rows = (height / tile_size).ceil() as usize;
 
@@ -85,13 +170,96 @@ impl Map {
             }
         }
 
-        Ok(Map {
+        let mut map = Map {
             width,
             height,
             tiles,
             obstacles,
-            tileset,
-        })
+            obstacle_index: SpatialHash::new(),
+            markers: MapMarkers::default(),
+        };
+        map.rebuild_obstacle_index();
+        map
+    }
+
+    // Carte vide à partir de tuiles rangées ligne par ligne, comme dans Tiled
+    pub fn from_rows(cols: usize, rows: usize, tile_types: &[TileType]) -> Self {
+        let mut tiles = Vec::with_capacity(cols * rows);
+        for x in 0..cols {
+            for y in 0..rows {
+                tiles.push(Tile {
+                    position: Point2 {
+                        x: x as f32 * TILE_SIZE + TILE_SIZE / 2.0,
+                        y: y as f32 * TILE_SIZE + TILE_SIZE / 2.0,
+                    },
+                    tile_type: tile_types.get(y * cols + x).copied().unwrap_or(TileType::Grass),
+                });
+            }
+        }
+
+        Map {
+            width: cols as f32 * TILE_SIZE,
+            height: rows as f32 * TILE_SIZE,
+            tiles,
+            obstacles: Vec::new(),
+            obstacle_index: SpatialHash::new(),
+            markers: MapMarkers::default(),
+        }
+    }
+
+    pub fn rebuild_obstacle_index(&mut self) {
+        self.obstacle_index.rebuild(self.obstacles.iter().map(Obstacle::collider));
+    }
+
+    pub fn remove_last_obstacle(&mut self) {
+        if let Some(obstacle) = self.obstacles.pop() {
+            self.obstacle_index.remove(self.obstacles.len(), &obstacle.collider());
+        }
+    }
+
+    pub fn add_obstacle(&mut self, obstacle_type: ObstacleType, position: Point2<f32>, width: f32, height: f32) {
+        let obstacle = Obstacle {
+            position,
+            width,
+            height,
+            obstacle_type,
+        };
+        self.obstacle_index.insert(self.obstacles.len(), &obstacle.collider());
+        self.obstacles.push(obstacle);
+    }
+
+    fn obstacles_near<'a>(&'a self, collider: &Collider) -> impl Iterator<Item = &'a Obstacle> {
+        self.obstacle_index.query(collider).into_iter().map(|index| &self.obstacles[index])
+    }
+
+    pub fn center(&self) -> Point2<f32> {
+        Point2 { x: self.width / 2.0, y: self.height / 2.0 }
+    }
+
+    pub fn tile_at(&self, position: Point2<f32>) -> Option<TileType> {
+        if position.x < 0.0 || position.y < 0.0 {
+            return None;
+        }
+
+        let cols = (self.width / TILE_SIZE).ceil() as usize;
+        let rows = (self.height / TILE_SIZE).ceil() as usize;
+        let col = (position.x / TILE_SIZE) as usize;
+        let row = (position.y / TILE_SIZE) as usize;
+        if col >= cols || row >= rows {
+            return None;
+        }
+
+        // Les tuiles sont rangées colonne par colonne (voir Map::new)
+        self.tiles.get(col * rows + row).map(|tile| tile.tile_type)
+    }
+
+    pub fn speed_multiplier_at(&self, position: Point2<f32>) -> f32 {
+        self.tile_at(position).map_or(1.0, TileType::speed_multiplier)
+    }
+
+    pub fn can_spawn_at(&self, position: Point2<f32>, radius: f32) -> bool {
+        self.is_position_valid(position, radius) &&
+            self.tile_at(position).is_some_and(TileType::allows_spawn)
     }
 
     pub fn is_position_valid(&self, position: Point2<f32>, radius: f32) -> bool {
@@ -101,94 +269,205 @@ impl Map {
             return false;
         }
 
-       
-        for obstacle in &self.obstacles {
-            let dx = obstacle.position.x - position.x;
-            let dy = obstacle.position.y - position.y;
-            let distance = (dx * dx + dy * dy).sqrt();
-            
-            if distance < (obstacle.width / 2.0 + radius) {
-                return false;
+        let collider = Collider::circle(position, radius);
+        !self.obstacles_near(&collider).any(|obstacle| collider.intersects(&obstacle.collider()))
+    }
+
+    pub fn blocks(&self, collider: &Collider) -> bool {
+        let center = collider.center;
+        if center.x < 0.0 || center.x > self.width || center.y < 0.0 || center.y > self.height {
+            return true;
+        }
+
+        self.obstacles_near(collider).any(|obstacle| collider.intersects(&obstacle.collider()))
+    }
+
+    pub fn move_with_collisions(&self, position: Point2<f32>, delta: Point2<f32>, radius: f32) -> Point2<f32> {
+        let mut target = Point2 {
+            x: position.x + delta.x,
+            y: position.y + delta.y,
+        };
+
+        // On repousse la cible hors de chaque obstacle le long de la normale,
+        // ce qui fait glisser l'entité le long de la surface au lieu de la bloquer
+        for _ in 0..3 {
+            let mut pushed = false;
+
+            let nearby = self.obstacle_index.query_radius(target, radius * 2.0);
+            for obstacle in nearby.into_iter().map(|index| &self.obstacles[index]) {
+                let collider = Collider::circle(target, radius);
+                if let Some(push) = collider.penetration(&obstacle.collider()) {
+                    target.x += push.x;
+                    target.y += push.y;
+                    pushed = true;
+                }
+            }
+
+            if !pushed {
+                break;
             }
         }
 
-        true
+        target.x = target.x.clamp(radius, self.width - radius);
+        target.y = target.y.clamp(radius, self.height - radius);
+        target
     }
+}
 
-    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult<()> {
-        let tile_size = 64.0;
+const TILESET_COLUMNS: u32 = 4;
+const TILESET_ROWS: u32 = 4;
+// Une tuile sur VARIANT_FREQUENCY utilise la variante de la deuxième ligne du tileset
+const VARIANT_FREQUENCY: usize = 4;
 
-     
-        for tile in &self.tiles {
-            let color = match tile.tile_type {
-                TileType::Grass => Color::from_rgb(100, 200, 100),
-                TileType::Path => Color::from_rgb(200, 180, 100),
-                TileType::Water => Color::from_rgb(100, 150, 255),
-                TileType::Stone => Color::from_rgb(150, 150, 150),
-            };
+// Côté d'un morceau de carte, en tuiles
+const CHUNK_TILES: usize = 8;
+// Les obstacles débordent un peu de leur morceau
+const CHUNK_MARGIN: f32 = TILE_SIZE;
+
+// Géométrie de la carte construite une seule fois par morceaux de 8 x 8 tuiles ;
+// seuls les morceaux qui recoupent la vue sont dessinés
+pub struct MapRenderer {
+    chunks: Vec<MapChunk>,
+    built: bool,
+}
+
+struct MapChunk {
+    bounds: Rect,
+    tiles: Option<ChunkTiles>,
+    obstacles: Option<Mesh>,
+}
+
+enum ChunkTiles {
+    Tileset(InstanceArray),
+    // Sans tileset : rectangles de couleur et quadrillage, réunis dans un seul mesh
+    Colored(Mesh),
+}
+
+impl MapRenderer {
+    pub fn new() -> Self {
+        MapRenderer {
+            chunks: Vec::new(),
+            built: false,
+        }
+    }
 
-            let tile_mesh = Mesh::new_rectangle(
-                ctx,
-                graphics::DrawMode::fill(),
-                graphics::Rect::new(
-                    tile.position.x - tile_size / 2.0,
-                    tile.position.y - tile_size / 2.0,
-                    tile_size,
-                    tile_size,
-                ),
-                color,
-            )?;
-            canvas.draw(&tile_mesh, DrawParam::default());
-
-          
-            let border = Mesh::new_rectangle(
-                ctx,
-                graphics::DrawMode::stroke(1.0),
-                graphics::Rect::new(
-                    tile.position.x - tile_size / 2.0,
-                    tile.position.y - tile_size / 2.0,
-                    tile_size,
-                    tile_size,
-                ),
-                Color::from_rgba(255, 255, 255, 50),
-            )?;
-            canvas.draw(&border, DrawParam::default());
-        }
-
-       
-        for obstacle in &self.obstacles {
-            let (color, shape) = match obstacle.obstacle_type {
-                ObstacleType::Tree => (Color::from_rgb(50, 120, 50), "circle"),
-                ObstacleType::Rock => (Color::from_rgb(120, 120, 120), "rectangle"),
-                ObstacleType::Bush => (Color::from_rgb(80, 160, 80), "circle"),
+    // À appeler quand la carte ou le tileset sont remplacés
+    pub fn invalidate(&mut self) {
+        self.built = false;
+    }
+
+    pub fn draw(
+        &mut self,
+        ctx: &mut Context,
+        canvas: &mut Canvas,
+        map: &Map,
+        tileset: Option<&Image>,
+        view: Rect,
+    ) -> GameResult<()> {
+        if !self.built {
+            self.build(ctx, map, tileset)?;
+        }
+
+        for chunk in self.chunks.iter().filter(|chunk| chunk.bounds.overlaps(&view)) {
+            match &chunk.tiles {
+                Some(ChunkTiles::Tileset(instances)) => canvas.draw(instances, DrawParam::default()),
+                Some(ChunkTiles::Colored(mesh)) => canvas.draw(mesh, DrawParam::default()),
+                None => {}
+            }
+            if let Some(obstacles) = &chunk.obstacles {
+                canvas.draw(obstacles, DrawParam::default());
+            }
+        }
+        Ok(())
+    }
+
+    fn build(&mut self, ctx: &mut Context, map: &Map, tileset: Option<&Image>) -> GameResult<()> {
+        let chunk_size = CHUNK_TILES as f32 * TILE_SIZE;
+        let chunk_cols = (map.width / chunk_size).ceil().max(1.0) as usize;
+        let chunk_rows = (map.height / chunk_size).ceil().max(1.0) as usize;
+        let chunk_of = |position: Point2<f32>| {
+            let col = ((position.x / chunk_size).max(0.0) as usize).min(chunk_cols - 1);
+            let row = ((position.y / chunk_size).max(0.0) as usize).min(chunk_rows - 1);
+            row * chunk_cols + col
+        };
+
+        let mut tiles: Vec<Vec<&Tile>> = vec![Vec::new(); chunk_cols * chunk_rows];
+        for tile in &map.tiles {
+            tiles[chunk_of(tile.position)].push(tile);
+        }
+        let mut obstacles: Vec<Vec<&Obstacle>> = vec![Vec::new(); chunk_cols * chunk_rows];
+        for obstacle in &map.obstacles {
+            obstacles[chunk_of(obstacle.position)].push(obstacle);
+        }
+
+        self.chunks.clear();
+        for (index, (tiles, obstacles)) in tiles.into_iter().zip(obstacles).enumerate() {
+            let (col, row) = (index % chunk_cols, index / chunk_cols);
+            let bounds = Rect::new(
+                col as f32 * chunk_size - CHUNK_MARGIN,
+                row as f32 * chunk_size - CHUNK_MARGIN,
+                chunk_size + CHUNK_MARGIN * 2.0,
+                chunk_size + CHUNK_MARGIN * 2.0,
+            );
+
+            let tiles = if tiles.is_empty() {
+                None
+            } else if let Some(tileset) = tileset {
+                Some(ChunkTiles::Tileset(tile_instances(ctx, tileset, &tiles)))
+            } else {
+                Some(ChunkTiles::Colored(tile_mesh(ctx, &tiles)?))
             };
 
-            let obstacle_mesh = if shape == "circle" {
-                Mesh::new_circle(
-                    ctx,
-                    graphics::DrawMode::fill(),
-                    Point2 { x: 0.0, y: 0.0 },
-                    obstacle.width / 2.0,
-                    0.1,
-                    color,
-                )?
+            let obstacles = if obstacles.is_empty() {
+                None
             } else {
-                Mesh::new_rectangle(
-                    ctx,
-                    graphics::DrawMode::fill(),
-                    graphics::Rect::new(
-                        -obstacle.width / 2.0,
-                        -obstacle.height / 2.0,
-                        obstacle.width,
-                        obstacle.height,
-                    ),
-                    color,
-                )?
+                let mut builder = MeshBuilder::new();
+                for obstacle in obstacles {
+                    obstacle.add_to(&mut builder)?;
+                }
+                Some(Mesh::from_data(ctx, builder.build()))
             };
 
-            canvas.draw(&obstacle_mesh, DrawParam::default().dest(obstacle.position));
+            self.chunks.push(MapChunk { bounds, tiles, obstacles });
         }
 
+        self.built = true;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+fn tile_instances(ctx: &mut Context, tileset: &Image, tiles: &[&Tile]) -> InstanceArray {
+    let width = 1.0 / TILESET_COLUMNS as f32;
+    let height = 1.0 / TILESET_ROWS as f32;
+    let scale = TILE_SIZE / (tileset.width() as f32 * width);
+
+    let mut instances = InstanceArray::new(ctx, tileset.clone());
+    instances.set(tiles.iter().map(|tile| {
+        let col = (tile.position.x / TILE_SIZE) as usize;
+        let row = (tile.position.y / TILE_SIZE) as usize;
+        // Variante choisie d'après la position pour rester identique d'une construction à l'autre
+        let variant = if (col * 7 + row * 13).is_multiple_of(VARIANT_FREQUENCY) { 1.0 } else { 0.0 };
+        let source = Rect::new(tile.tile_type.tileset_column() as f32 * width, variant * height, width, height);
+
+        DrawParam::default()
+            .src(source)
+            .dest([tile.position.x - TILE_SIZE / 2.0, tile.position.y - TILE_SIZE / 2.0])
+            .scale([scale, scale])
+    }));
+    instances
+}
+
+fn tile_mesh(ctx: &mut Context, tiles: &[&Tile]) -> GameResult<Mesh> {
+    let mut builder = MeshBuilder::new();
+    for tile in tiles {
+        let bounds = Rect::new(
+            tile.position.x - TILE_SIZE / 2.0,
+            tile.position.y - TILE_SIZE / 2.0,
+            TILE_SIZE,
+            TILE_SIZE,
+        );
+        builder.rectangle(graphics::DrawMode::fill(), bounds, tile.tile_type.color())?;
+        builder.rectangle(graphics::DrawMode::stroke(1.0), bounds, Color::from_rgba(255, 255, 255, 50))?;
+    }
+    Ok(Mesh::from_data(ctx, builder.build()))
+}
@@ -0,0 +1,278 @@
+use ggez::mint::Point2;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+use crate::map::{Map, ObstacleType, TileType, TILE_SIZE};
+use crate::pathfinding::{cell_center, NAV_CELL_SIZE};
+use crate::player::PLAYER_RADIUS;
+
+const WATER_LEVEL: f32 = 0.4;
+const STONE_LEVEL: f32 = 0.6;
+const SMOOTHING_PASSES: usize = 3;
+const PATH_COUNT: usize = 3;
+// Nombre de tuiles par groupe d'obstacles
+const TILES_PER_CLUSTER: usize = 10;
+// Aucun obstacle aussi près du point de départ
+const START_CLEARING: f32 = 96.0;
+
+// Carte aléatoire reproductible à partir d'une graine : bruit de valeur pour le relief,
+// automate cellulaire pour lisser les biomes, puis chemins et groupes d'obstacles
+pub fn generate(cols: usize, rows: usize, seed: u64) -> Map {
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+
+    let coarse = value_noise(&mut rng, cols, rows, 4);
+    let fine = value_noise(&mut rng, cols, rows, 2);
+    let mut tiles: Vec<TileType> = coarse.iter()
+        .zip(&fine)
+        .map(|(coarse, fine)| {
+            let elevation = coarse * 0.65 + fine * 0.35;
+            if elevation < WATER_LEVEL {
+                TileType::Water
+            } else if elevation > STONE_LEVEL {
+                TileType::Stone
+            } else {
+                TileType::Grass
+            }
+        })
+        .collect();
+
+    for _ in 0..SMOOTHING_PASSES {
+        tiles = smooth(&tiles, cols, rows);
+    }
+
+    let start = (cols / 2, rows / 2);
+    carve_paths(&mut rng, &mut tiles, cols, rows, start);
+
+    let mut map = Map::from_rows(cols, rows, &tiles);
+    let start_position = tile_center(start.0, start.1);
+    map.markers.player_spawn = Some(start_position);
+
+    place_obstacles(&mut rng, &mut map, &tiles, cols, rows, start_position);
+
+    map
+}
+
+// Valeurs aléatoires tous les `step` tuiles, interpolées entre elles
+fn value_noise(rng: &mut ChaCha12Rng, cols: usize, rows: usize, step: usize) -> Vec<f32> {
+    let lattice_cols = cols / step + 2;
+    let lattice_rows = rows / step + 2;
+    let lattice: Vec<f32> = (0..lattice_cols * lattice_rows).map(|_| rng.gen()).collect();
+    let at = |col: usize, row: usize| lattice[row * lattice_cols + col];
+    let smoothstep = |t: f32| t * t * (3.0 - 2.0 * t);
+
+    let mut values = Vec::with_capacity(cols * rows);
+    for row in 0..rows {
+        for col in 0..cols {
+            let (cell_col, cell_row) = (col / step, row / step);
+            let tx = smoothstep((col % step) as f32 / step as f32);
+            let ty = smoothstep((row % step) as f32 / step as f32);
+
+            let top = at(cell_col, cell_row) * (1.0 - tx) + at(cell_col + 1, cell_row) * tx;
+            let bottom = at(cell_col, cell_row + 1) * (1.0 - tx) + at(cell_col + 1, cell_row + 1) * tx;
+            values.push(top * (1.0 - ty) + bottom * ty);
+        }
+    }
+    values
+}
+
+// Une tuile prend le terrain majoritaire (5 sur 9) de son voisinage
+fn smooth(tiles: &[TileType], cols: usize, rows: usize) -> Vec<TileType> {
+    let mut smoothed = tiles.to_vec();
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let mut counts = [0; 4];
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let x = (col as i32 + dx).clamp(0, cols as i32 - 1) as usize;
+                    let y = (row as i32 + dy).clamp(0, rows as i32 - 1) as usize;
+                    counts[terrain_index(tiles[y * cols + x])] += 1;
+                }
+            }
+
+            if let Some(majority) = counts.iter().position(|&count| count >= 5) {
+                smoothed[row * cols + col] = TERRAINS[majority];
+            }
+        }
+    }
+    smoothed
+}
+
+const TERRAINS: [TileType; 4] = [TileType::Grass, TileType::Path, TileType::Water, TileType::Stone];
+
+fn terrain_index(tile_type: TileType) -> usize {
+    TERRAINS.iter().position(|&terrain| terrain == tile_type).unwrap_or(0)
+}
+
+// Chemins en L du départ vers des tuiles au hasard ; ils traversent l'eau comme des gués
+fn carve_paths(rng: &mut ChaCha12Rng, tiles: &mut [TileType], cols: usize, rows: usize, start: (usize, usize)) {
+    for _ in 0..PATH_COUNT {
+        let target = (rng.gen_range(0..cols), rng.gen_range(0..rows));
+        let (mut col, mut row) = start;
+
+        while col != target.0 {
+            tiles[row * cols + col] = TileType::Path;
+            col = if col < target.0 { col + 1 } else { col - 1 };
+        }
+        while row != target.1 {
+            tiles[row * cols + col] = TileType::Path;
+            row = if row < target.1 { row + 1 } else { row - 1 };
+        }
+        tiles[row * cols + col] = TileType::Path;
+    }
+}
+
+fn place_obstacles(
+    rng: &mut ChaCha12Rng,
+    map: &mut Map,
+    tiles: &[TileType],
+    cols: usize,
+    rows: usize,
+    start: Point2<f32>,
+) {
+    let clusters = (cols * rows / TILES_PER_CLUSTER).max(1);
+    let mut walkable = WalkGrid::new(map);
+
+    for _ in 0..clusters {
+        let (col, row) = (rng.gen_range(0..cols), rng.gen_range(0..rows));
+        // Rochers sur la pierre, arbres et buissons sur l'herbe
+        let obstacle_type = match tiles[row * cols + col] {
+            TileType::Stone => ObstacleType::Rock,
+            TileType::Grass if rng.gen_bool(0.6) => ObstacleType::Tree,
+            TileType::Grass => ObstacleType::Bush,
+            TileType::Path | TileType::Water => continue,
+        };
+        let center = tile_center(col, row);
+
+        for _ in 0..rng.gen_range(2..=4) {
+            let size = rng.gen_range(30.0..44.0);
+            let position = Point2 {
+                x: center.x + rng.gen_range(-40.0..40.0),
+                y: center.y + rng.gen_range(-40.0..40.0),
+            };
+
+            let blocked_tile = matches!(map.tile_at(position), None | Some(TileType::Path | TileType::Water));
+            let near_start = (position.x - start.x).hypot(position.y - start.y) < START_CLEARING;
+            if blocked_tile || near_start || !map.is_position_valid(position, size / 2.0) {
+                continue;
+            }
+
+            // On retire tout obstacle qui isolerait une partie de la carte
+            map.add_obstacle(obstacle_type, position, size, size);
+            let covered = walkable.block_around(map, position, size);
+            if !walkable.is_connected_from(start) {
+                walkable.unblock(&covered);
+                map.remove_last_obstacle();
+            }
+        }
+    }
+}
+
+// Cellules praticables par le héros, tenue à jour obstacle par obstacle
+struct WalkGrid {
+    cols: usize,
+    rows: usize,
+    free: Vec<bool>,
+}
+
+impl WalkGrid {
+    fn new(map: &Map) -> Self {
+        let cols = (map.width / NAV_CELL_SIZE).ceil() as usize;
+        let rows = (map.height / NAV_CELL_SIZE).ceil() as usize;
+        let mut free = Vec::with_capacity(cols * rows);
+        for row in 0..rows {
+            for col in 0..cols {
+                free.push(map.is_position_valid(cell_center(col, row), PLAYER_RADIUS));
+            }
+        }
+        WalkGrid { cols, rows, free }
+    }
+
+    // Bloque les cellules que le nouvel obstacle rend impraticables et les renvoie
+    fn block_around(&mut self, map: &Map, position: Point2<f32>, size: f32) -> Vec<usize> {
+        let reach = size / 2.0 + PLAYER_RADIUS + NAV_CELL_SIZE;
+        let first_col = ((position.x - reach) / NAV_CELL_SIZE).max(0.0) as usize;
+        let first_row = ((position.y - reach) / NAV_CELL_SIZE).max(0.0) as usize;
+        let last_col = (((position.x + reach) / NAV_CELL_SIZE) as usize).min(self.cols - 1);
+        let last_row = (((position.y + reach) / NAV_CELL_SIZE) as usize).min(self.rows - 1);
+
+        let mut covered = Vec::new();
+        for row in first_row..=last_row {
+            for col in first_col..=last_col {
+                let index = row * self.cols + col;
+                if self.free[index] && !map.is_position_valid(cell_center(col, row), PLAYER_RADIUS) {
+                    self.free[index] = false;
+                    covered.push(index);
+                }
+            }
+        }
+        covered
+    }
+
+    fn unblock(&mut self, cells: &[usize]) {
+        for &index in cells {
+            self.free[index] = true;
+        }
+    }
+
+    fn is_connected_from(&self, start: Point2<f32>) -> bool {
+        let start_col = (start.x / NAV_CELL_SIZE) as usize;
+        let start_row = (start.y / NAV_CELL_SIZE) as usize;
+        let start_index = start_row * self.cols + start_col;
+        if !self.free[start_index] {
+            return false;
+        }
+
+        let mut visited = vec![false; self.free.len()];
+        let mut stack = vec![start_index];
+        let mut reached = 0;
+        visited[start_index] = true;
+
+        while let Some(index) = stack.pop() {
+            reached += 1;
+            let (col, row) = (index % self.cols, index / self.cols);
+            let neighbours = [
+                (col > 0).then(|| index - 1),
+                (col + 1 < self.cols).then(|| index + 1),
+                (row > 0).then(|| index - self.cols),
+                (row + 1 < self.rows).then(|| index + self.cols),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if self.free[next] && !visited[next] {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        reached == self.free.iter().filter(|&&free| free).count()
+    }
+}
+
+fn tile_center(col: usize, row: usize) -> Point2<f32> {
+    Point2 {
+        x: col as f32 * TILE_SIZE + TILE_SIZE / 2.0,
+        y: row as f32 * TILE_SIZE + TILE_SIZE / 2.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_maps_stay_connected() {
+        for seed in 0..4 {
+            let map = generate(40, 30, seed);
+            let start = map.markers.player_spawn.unwrap();
+            assert!(WalkGrid::new(&map).is_connected_from(start), "graine {}", seed);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_map() {
+        let a = serde_json::to_string(&generate(40, 30, 7)).unwrap();
+        let b = serde_json::to_string(&generate(40, 30, 7)).unwrap();
+        assert_eq!(a, b);
+    }
+}
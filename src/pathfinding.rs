@@ -0,0 +1,236 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use ggez::mint::Point2;
+
+use crate::map::Map;
+
+pub const NAV_CELL_SIZE: f32 = 16.0;
+
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (1, 0), (-1, 0), (0, 1), (0, -1),
+    (1, 1), (1, -1), (-1, 1), (-1, -1),
+];
+
+// Grille de navigation dérivée des tuiles et obstacles de la carte.
+// `None` marque une cellule bloquée, sinon le coût de traversée.
+#[derive(Debug, Default)]
+pub struct NavGrid {
+    cols: usize,
+    rows: usize,
+    costs: Vec<Option<f32>>,
+    min_cost: f32,
+}
+
+#[derive(Debug, PartialEq)]
+struct OpenNode {
+    index: usize,
+    cost: f32,
+    estimate: f32,
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap est un tas max : on inverse pour sortir le plus petit coût
+        other.estimate.total_cmp(&self.estimate)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl NavGrid {
+    pub fn build(map: &Map, clearance: f32) -> Self {
+        let cols = (map.width / NAV_CELL_SIZE).ceil() as usize;
+        let rows = (map.height / NAV_CELL_SIZE).ceil() as usize;
+        let mut costs = Vec::with_capacity(cols * rows);
+        let mut min_cost = f32::MAX;
+
+        for row in 0..rows {
+            for col in 0..cols {
+                let center = cell_center(col, row);
+                let cost = if map.is_position_valid(center, clearance) {
+                    let cost = 1.0 / map.speed_multiplier_at(center);
+                    min_cost = min_cost.min(cost);
+                    Some(cost)
+                } else {
+                    None
+                };
+                costs.push(cost);
+            }
+        }
+
+        NavGrid {
+            cols,
+            rows,
+            costs,
+            min_cost: if min_cost == f32::MAX { 1.0 } else { min_cost },
+        }
+    }
+
+    pub fn cell_of(&self, position: Point2<f32>) -> Option<usize> {
+        if position.x < 0.0 || position.y < 0.0 {
+            return None;
+        }
+        let col = (position.x / NAV_CELL_SIZE) as usize;
+        let row = (position.y / NAV_CELL_SIZE) as usize;
+        if col >= self.cols || row >= self.rows {
+            return None;
+        }
+        Some(row * self.cols + col)
+    }
+
+    pub fn is_walkable(&self, position: Point2<f32>) -> bool {
+        self.cell_of(position).is_some_and(|index| self.costs[index].is_some())
+    }
+
+    // Chemin A* sous forme de points de passage, du départ (exclu) jusqu'au but
+    pub fn find_path(&self, start: Point2<f32>, goal: Point2<f32>) -> Option<Vec<Point2<f32>>> {
+        let start_index = self.cell_of(start)?;
+        let goal_index = self.cell_of(goal)?;
+
+        if start_index == goal_index {
+            return Some(vec![goal]);
+        }
+
+        let mut best_cost = vec![f32::INFINITY; self.costs.len()];
+        let mut came_from = vec![usize::MAX; self.costs.len()];
+        let mut open = BinaryHeap::new();
+
+        best_cost[start_index] = 0.0;
+        open.push(OpenNode {
+            index: start_index,
+            cost: 0.0,
+            estimate: self.heuristic(start_index, goal_index),
+        });
+
+        while let Some(OpenNode { index, cost, .. }) = open.pop() {
+            if index == goal_index {
+                return Some(self.rebuild_path(&came_from, goal_index, goal));
+            }
+            if cost > best_cost[index] {
+                continue;
+            }
+
+            let (col, row) = (index % self.cols, index / self.cols);
+            let Some(from_cost) = self.cell_cost(index, start_index, goal_index) else {
+                continue;
+            };
+
+            for (dx, dy) in NEIGHBOURS {
+                let next_col = col as i32 + dx;
+                let next_row = row as i32 + dy;
+                let Some(next) = self.index_of(next_col, next_row) else {
+                    continue;
+                };
+                let Some(to_cost) = self.cell_cost(next, start_index, goal_index) else {
+                    continue;
+                };
+
+                // Pas de coupe de coin à travers un obstacle en diagonale
+                if dx != 0 && dy != 0 {
+                    let side_a = self.index_of(col as i32 + dx, row as i32);
+                    let side_b = self.index_of(col as i32, row as i32 + dy);
+                    let clear = |side: Option<usize>| {
+                        side.is_some_and(|i| self.cell_cost(i, start_index, goal_index).is_some())
+                    };
+                    if !clear(side_a) || !clear(side_b) {
+                        continue;
+                    }
+                }
+
+                let step = if dx != 0 && dy != 0 { std::f32::consts::SQRT_2 } else { 1.0 };
+                let next_cost = cost + step * (from_cost + to_cost) / 2.0;
+
+                if next_cost < best_cost[next] {
+                    best_cost[next] = next_cost;
+                    came_from[next] = index;
+                    open.push(OpenNode {
+                        index: next,
+                        cost: next_cost,
+                        estimate: next_cost + self.heuristic(next, goal_index),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    fn index_of(&self, col: i32, row: i32) -> Option<usize> {
+        if col < 0 || row < 0 || col as usize >= self.cols || row as usize >= self.rows {
+            return None;
+        }
+        Some(row as usize * self.cols + col as usize)
+    }
+
+    // Le départ et l'arrivée restent praticables même collés à un obstacle
+    fn cell_cost(&self, index: usize, start: usize, goal: usize) -> Option<f32> {
+        match self.costs[index] {
+            Some(cost) => Some(cost),
+            None if index == start || index == goal => Some(1.0),
+            None => None,
+        }
+    }
+
+    fn heuristic(&self, from: usize, to: usize) -> f32 {
+        let dx = (from % self.cols).abs_diff(to % self.cols) as f32;
+        let dy = (from / self.cols).abs_diff(to / self.cols) as f32;
+        let octile = dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy);
+        octile * self.min_cost
+    }
+
+    fn rebuild_path(&self, came_from: &[usize], goal_index: usize, goal: Point2<f32>) -> Vec<Point2<f32>> {
+        let mut path = vec![goal];
+        let mut current = came_from[goal_index];
+
+        while came_from[current] != usize::MAX {
+            path.push(cell_center(current % self.cols, current / self.cols));
+            current = came_from[current];
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+pub fn cell_center(col: usize, row: usize) -> Point2<f32> {
+    Point2 {
+        x: col as f32 * NAV_CELL_SIZE + NAV_CELL_SIZE / 2.0,
+        y: row as f32 * NAV_CELL_SIZE + NAV_CELL_SIZE / 2.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_goes_around_an_obstacle() {
+        // La carte par défaut place un arbre au centre de la tuile (3, 3)
+        let map = Map::new(800.0, 600.0);
+        let grid = NavGrid::build(&map, 12.0);
+        let tree = Point2 { x: 224.0, y: 224.0 };
+        assert!(!grid.is_walkable(tree));
+
+        let start = Point2 { x: 160.0, y: 224.0 };
+        let goal = Point2 { x: 288.0, y: 224.0 };
+        let path = grid.find_path(start, goal).unwrap();
+
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.iter().all(|&point| grid.is_walkable(point)));
+        assert!(path.iter().any(|point| (point.y - tree.y).abs() > 20.0));
+    }
+
+    #[test]
+    fn no_path_outside_the_map() {
+        let grid = NavGrid::build(&Map::new(800.0, 600.0), 12.0);
+        assert_eq!(grid.find_path(Point2 { x: 160.0, y: 224.0 }, Point2 { x: 900.0, y: 224.0 }), None);
+    }
+}
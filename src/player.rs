@@ -1,8 +1,25 @@
 use ggez::{Context, GameResult, graphics};
-use ggez::graphics::{Canvas, DrawParam, Color, Image, Mesh};
+use ggez::graphics::{Canvas, DrawParam, Color, Mesh};
 use ggez::mint::Point2;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+use crate::animation::{AnimationFrame, Clip, SpriteSheet};
+use crate::collision::{self, Collider};
+use crate::inventory::Inventory;
+use crate::map::Map;
+use crate::projectile::{Projectile, ProjectileOwner};
+use crate::weapon::{WeaponDefinition, WeaponKind};
+
+pub const PLAYER_RADIUS: f32 = 15.0;
+// Durée pendant laquelle la zone de frappe reste active
+const SWING_DURATION: f32 = 0.3;
+const INVULNERABILITY_DURATION: f32 = 0.8;
+const KNOCKBACK_STRENGTH: f32 = 280.0;
+// Durée de l'animation de douleur au début de l'invulnérabilité
+const HURT_ANIMATION: f32 = 0.3;
+const SPRITE_SCALE: f32 = 0.4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
     Left,
     Right,
@@ -10,7 +27,26 @@ pub enum Direction {
     Down,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StatChoice {
+    MaxHealth,
+    Speed,
+    Damage,
+}
+
+impl StatChoice {
+    pub const ALL: [StatChoice; 3] = [StatChoice::MaxHealth, StatChoice::Speed, StatChoice::Damage];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatChoice::MaxHealth => "+15 PV max",
+            StatChoice::Speed => "+15 vitesse",
+            StatChoice::Damage => "+3 dégâts",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Player {
     pub position: Point2<f32>,
     pub velocity: Point2<f32>,
@@ -20,16 +56,25 @@ pub struct Player {
     pub direction: Direction,
     pub speed: f32,
     pub is_attacking: bool,
+    // Temps avant de pouvoir frapper de nouveau
     pub attack_timer: f32,
-    pub weapon_level: u32,
-    sprite: Option<Image>,
+    swing_timer: f32,
+    pub weapon: WeaponKind,
+    // Identifiants des ennemis déjà touchés par le coup en cours
+    swing_hits: Vec<u32>,
+    pub invulnerable_timer: f32,
+    knockback: Point2<f32>,
+    pub inventory: Inventory,
+    pub level: u32,
+    pub xp: u32,
+    // Améliorations gagnées mais pas encore choisies
+    pub pending_level_ups: u32,
+    bonus_damage: i32,
 }
 
 impl Player {
-    pub fn new(ctx: &mut Context, x: f32, y: f32) -> GameResult<Self> {
-        let sprite = Image::from_path(ctx, "/player.png").ok();
-        
-        Ok(Player {
+    pub fn new(x: f32, y: f32) -> Self {
+        Player {
             position: Point2 { x, y },
             velocity: Point2 { x: 0.0, y: 0.0 },
             health: 100,
@@ -39,20 +84,39 @@ impl Player {
             speed: 200.0,
             is_attacking: false,
             attack_timer: 0.0,
-            weapon_level: 1,
-            sprite,
-        })
+            swing_timer: 0.0,
+            weapon: WeaponKind::Sword,
+            swing_hits: Vec::new(),
+            invulnerable_timer: 0.0,
+            knockback: Point2 { x: 0.0, y: 0.0 },
+            inventory: Inventory::new(),
+            level: 1,
+            xp: 0,
+            pending_level_ups: 0,
+            bonus_damage: 0,
+        }
     }
 
-    pub fn update(&mut self, dt: f32, map_width: f32, map_height: f32) {
-        self.position.x += self.velocity.x * dt;
-        self.position.y += self.velocity.y * dt;
-        self.position.x = self.position.x.clamp(20.0, map_width - 20.0);
-        self.position.y = self.position.y.clamp(20.0, map_height - 20.0);
+    pub fn update(&mut self, dt: f32, map: &Map) {
+        let terrain = map.speed_multiplier_at(self.position);
+        let delta = Point2 {
+            x: (self.velocity.x * terrain + self.knockback.x) * dt,
+            y: (self.velocity.y * terrain + self.knockback.y) * dt,
+        };
+        self.knockback = collision::damp_knockback(self.knockback, dt);
+        self.position = map.move_with_collisions(self.position, delta, PLAYER_RADIUS);
 
-        if self.is_attacking {
+        if self.invulnerable_timer > 0.0 {
+            self.invulnerable_timer -= dt;
+        }
+
+        if self.attack_timer > 0.0 {
             self.attack_timer -= dt;
-            if self.attack_timer <= 0.0 {
+        }
+
+        if self.is_attacking {
+            self.swing_timer -= dt;
+            if self.swing_timer <= 0.0 {
                 self.is_attacking = false;
             }
         }
@@ -70,9 +134,11 @@ impl Player {
             }
             Direction::Up => {
                 self.velocity.y = -self.speed;
+                self.direction = Direction::Up;
             }
             Direction::Down => {
                 self.velocity.y = self.speed;
+                self.direction = Direction::Down;
             }
         }
     }
@@ -82,59 +148,178 @@ impl Player {
         self.velocity.y = 0.0;
     }
 
-    pub fn attack(&mut self) {
-        if !self.is_attacking {
-            self.is_attacking = true;
-            self.attack_timer = 0.3;
+    // Les armes à distance renvoient leur projectile au lieu de frapper
+    pub fn attack(&mut self) -> Option<Projectile> {
+        if self.attack_timer > 0.0 || self.is_attacking {
+            return None;
+        }
+        let weapon = self.weapon_definition();
+        self.attack_timer = weapon.cooldown;
+
+        if let Some(kind) = weapon.projectile {
+            return Some(
+                Projectile::new(self.position, self.aim_direction(), kind, ProjectileOwner::Player, self.get_attack_damage())
+                    .with_knockback(weapon.knockback),
+            );
+        }
+
+        self.is_attacking = true;
+        self.swing_timer = SWING_DURATION.min(weapon.cooldown);
+        self.swing_hits.clear();
+        None
+    }
+
+    pub fn weapon_definition(&self) -> &'static WeaponDefinition {
+        self.weapon.definition()
+    }
+
+    // Renvoie l'arme précédente
+    pub fn equip(&mut self, weapon: WeaponKind) -> WeaponKind {
+        std::mem::replace(&mut self.weapon, weapon)
+    }
+
+    // On tire dans le sens de la marche, sinon vers où le héros regarde
+    fn aim_direction(&self) -> Point2<f32> {
+        if self.velocity.x != 0.0 || self.velocity.y != 0.0 {
+            return self.velocity;
+        }
+        match self.direction {
+            Direction::Left => Point2 { x: -1.0, y: 0.0 },
+            Direction::Right => Point2 { x: 1.0, y: 0.0 },
+            Direction::Up => Point2 { x: 0.0, y: -1.0 },
+            Direction::Down => Point2 { x: 0.0, y: 1.0 },
         }
     }
 
-    pub fn take_damage(&mut self, damage: i32) {
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_timer > 0.0
+    }
+
+    // Renvoie faux si le coup est ignoré pendant l'invulnérabilité
+    pub fn take_damage(&mut self, damage: i32, from: Point2<f32>) -> bool {
+        if self.is_invulnerable() {
+            return false;
+        }
         self.health = (self.health - damage).max(0);
+        self.invulnerable_timer = INVULNERABILITY_DURATION;
+        self.knockback = collision::knockback_impulse(from, self.position, KNOCKBACK_STRENGTH);
+        true
     }
 
     pub fn heal(&mut self, amount: i32) {
         self.health = (self.health + amount).min(self.max_health);
     }
 
+    pub fn increase_max_health(&mut self, amount: i32) {
+        self.max_health += amount;
+        self.heal(amount);
+    }
+
     pub fn add_coin(&mut self) {
         self.coins += 1;
     }
 
-    pub fn upgrade_weapon(&mut self) {
-        self.weapon_level += 1;
+    pub fn get_attack_damage(&self) -> i32 {
+        self.weapon_definition().damage + self.bonus_damage
     }
 
-    pub fn get_attack_damage(&self) -> i32 {
-        match self.weapon_level {
-            1 => 10,
-            2 => 15,
-            3 => 20,
-            _ => 25,
+    // Expérience nécessaire pour passer au niveau suivant
+    pub fn xp_to_next_level(&self) -> u32 {
+        20 + 15 * (self.level - 1) * self.level / 2
+    }
+
+    // Renvoie le nombre de niveaux gagnés
+    pub fn gain_xp(&mut self, amount: u32) -> u32 {
+        self.xp += amount;
+        let mut levels = 0;
+        while self.xp >= self.xp_to_next_level() {
+            self.xp -= self.xp_to_next_level();
+            self.level += 1;
+            levels += 1;
+        }
+        self.pending_level_ups += levels;
+        levels
+    }
+
+    pub fn choose_stat(&mut self, choice: StatChoice) -> bool {
+        if self.pending_level_ups == 0 {
+            return false;
+        }
+        self.pending_level_ups -= 1;
+        match choice {
+            StatChoice::MaxHealth => self.increase_max_health(15),
+            StatChoice::Speed => self.speed += 15.0,
+            StatChoice::Damage => self.bonus_damage += 3,
+        }
+        true
+    }
+
+    pub fn collider(&self) -> Collider {
+        Collider::circle(self.position, PLAYER_RADIUS)
+    }
+
+    pub fn animation_clip(&self) -> Clip {
+        if !self.is_alive() {
+            Clip::Death
+        } else if self.invulnerable_timer > INVULNERABILITY_DURATION - HURT_ANIMATION {
+            Clip::Hurt
+        } else if self.is_attacking {
+            Clip::Attack
+        } else if self.velocity.x != 0.0 || self.velocity.y != 0.0 {
+            Clip::Walk(self.direction)
+        } else {
+            Clip::Idle
+        }
+    }
+
+    pub fn swing_hitbox(&self) -> Collider {
+        let weapon = self.weapon_definition();
+        let forward = PLAYER_RADIUS + weapon.reach / 2.0;
+        match self.direction {
+            Direction::Left => Collider::aabb(
+                Point2 { x: self.position.x - forward, y: self.position.y },
+                weapon.reach,
+                weapon.arc,
+            ),
+            Direction::Right => Collider::aabb(
+                Point2 { x: self.position.x + forward, y: self.position.y },
+                weapon.reach,
+                weapon.arc,
+            ),
+            Direction::Up => Collider::aabb(
+                Point2 { x: self.position.x, y: self.position.y - forward },
+                weapon.arc,
+                weapon.reach,
+            ),
+            Direction::Down => Collider::aabb(
+                Point2 { x: self.position.x, y: self.position.y + forward },
+                weapon.arc,
+                weapon.reach,
+            ),
         }
     }
 
+    // Vrai une seule fois par coup et par cible, quel que soit le nombre d'images
+    pub fn register_swing_hit(&mut self, target_id: u32) -> bool {
+        if self.swing_hits.contains(&target_id) {
+            return false;
+        }
+        self.swing_hits.push(target_id);
+        true
+    }
+
     pub fn is_alive(&self) -> bool {
         self.health > 0
     }
 
-    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult<()> {
-        if let Some(sprite) = &self.sprite {
-            let scale = if self.is_attacking { 1.2 } else { 1.0 };
-            let color = if self.is_attacking { 
-                Color::from_rgba(255, 255, 150, 255) 
-            } else { 
-                Color::WHITE 
-            };
-            
-            canvas.draw(
-                sprite,
-                DrawParam::default()
-                    .dest(self.position)
-                    .offset([0.5, 0.5])
-                    .scale([scale, scale])
-                    .color(color),
-            );
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas, sheet: Option<&SpriteSheet>, frame: AnimationFrame) -> GameResult<()> {
+        // Clignotement pendant les frames d'invulnérabilité
+        if self.is_alive() && self.is_invulnerable() && (self.invulnerable_timer * 20.0) as i32 % 2 == 0 {
+            return Ok(());
+        }
+
+        if let Some(sheet) = sheet {
+            sheet.draw(canvas, frame, DrawParam::default().dest(self.position), SPRITE_SCALE);
         } else {
           
             let color = if self.is_attacking { Color::YELLOW } else { Color::GREEN };
@@ -143,7 +328,7 @@ impl Player {
                 ctx,
                 graphics::DrawMode::fill(),
                 Point2 { x: 0.0, y: 0.0 },
-                15.0,
+                PLAYER_RADIUS,
                 0.1,
                 color,
             )?;
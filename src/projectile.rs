@@ -0,0 +1,148 @@
+use ggez::{Context, GameResult, graphics};
+use ggez::graphics::{Canvas, DrawParam, Color, Mesh};
+use ggez::mint::Point2;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::Collider;
+use crate::map::Map;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProjectileOwner {
+    Player,
+    Enemy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProjectileKind {
+    Arrow,
+    Fireball,
+}
+
+impl ProjectileKind {
+    fn speed(self) -> f32 {
+        match self {
+            ProjectileKind::Arrow => 380.0,
+            ProjectileKind::Fireball => 220.0,
+        }
+    }
+
+    fn radius(self) -> f32 {
+        match self {
+            ProjectileKind::Arrow => 4.0,
+            ProjectileKind::Fireball => 9.0,
+        }
+    }
+
+    fn knockback(self) -> f32 {
+        match self {
+            ProjectileKind::Arrow => 160.0,
+            ProjectileKind::Fireball => 260.0,
+        }
+    }
+
+    fn lifetime(self) -> f32 {
+        match self {
+            ProjectileKind::Arrow => 1.2,
+            ProjectileKind::Fireball => 2.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Projectile {
+    pub position: Point2<f32>,
+    pub velocity: Point2<f32>,
+    pub lifetime: f32,
+    pub owner: ProjectileOwner,
+    pub kind: ProjectileKind,
+    pub damage: i32,
+    pub knockback: f32,
+    pub is_active: bool,
+}
+
+impl Projectile {
+    // `direction` n'a pas besoin d'être normalisée
+    pub fn new(
+        position: Point2<f32>,
+        direction: Point2<f32>,
+        kind: ProjectileKind,
+        owner: ProjectileOwner,
+        damage: i32,
+    ) -> Self {
+        let length = (direction.x * direction.x + direction.y * direction.y).sqrt().max(f32::EPSILON);
+        let speed = kind.speed();
+
+        Projectile {
+            position,
+            velocity: Point2 {
+                x: direction.x / length * speed,
+                y: direction.y / length * speed,
+            },
+            lifetime: kind.lifetime(),
+            owner,
+            kind,
+            damage,
+            knockback: kind.knockback(),
+            is_active: true,
+        }
+    }
+
+    pub fn with_knockback(mut self, knockback: f32) -> Self {
+        self.knockback = knockback;
+        self
+    }
+
+    pub fn update(&mut self, dt: f32, map: &Map) {
+        if !self.is_active {
+            return;
+        }
+
+        self.position.x += self.velocity.x * dt;
+        self.position.y += self.velocity.y * dt;
+        self.lifetime -= dt;
+
+        if self.lifetime <= 0.0 || map.blocks(&self.collider()) {
+            self.is_active = false;
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        Collider::circle(self.position, self.kind.radius())
+    }
+
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult<()> {
+        if !self.is_active {
+            return Ok(());
+        }
+
+        match self.kind {
+            ProjectileKind::Arrow => {
+                let shaft = Mesh::new_rectangle(
+                    ctx,
+                    graphics::DrawMode::fill(),
+                    graphics::Rect::new(-8.0, -1.5, 16.0, 3.0),
+                    Color::from_rgb(120, 80, 40),
+                )?;
+                canvas.draw(
+                    &shaft,
+                    DrawParam::default()
+                        .dest(self.position)
+                        .rotation(self.velocity.y.atan2(self.velocity.x)),
+                );
+            }
+            ProjectileKind::Fireball => {
+                let fireball = Mesh::new_circle(
+                    ctx,
+                    graphics::DrawMode::fill(),
+                    Point2 { x: 0.0, y: 0.0 },
+                    self.kind.radius(),
+                    0.1,
+                    Color::from_rgb(255, 120, 0),
+                )?;
+                canvas.draw(&fireball, DrawParam::default().dest(self.position));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::world::Command;
+
+pub const REPLAY_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RecordedInput {
+    pub frame: u64,
+    pub command: Command,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    // Partie jouée sur une carte générée plutôt que sur la carte Tiled
+    #[serde(default)]
+    pub generated_map: bool,
+    pub inputs: Vec<RecordedInput>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, generated_map: bool) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            seed,
+            generated_map,
+            inputs: Vec::new(),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let json = fs::read_to_string(path)?;
+        let replay: Replay = serde_json::from_str(&json)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "version de replay {} non supportée (attendue: {})",
+                replay.version, REPLAY_VERSION
+            ).into());
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+pub struct Recorder {
+    replay: Replay,
+    path: PathBuf,
+}
+
+impl Recorder {
+    pub fn new(seed: u64, generated_map: bool, path: PathBuf) -> Self {
+        Recorder {
+            replay: Replay::new(seed, generated_map),
+            path,
+        }
+    }
+
+    pub fn record(&mut self, frame: u64, command: Command) {
+        self.replay.inputs.push(RecordedInput { frame, command });
+    }
+
+    pub fn restart(&mut self, seed: u64) {
+        self.replay = Replay::new(seed, self.replay.generated_map);
+    }
+
+    pub fn flush(&self) {
+        match self.replay.save(&self.path) {
+            Ok(()) => println!(" Replay enregistré dans {}", self.path.display()),
+            Err(e) => println!(" Échec de l'enregistrement du replay: {}", e),
+        }
+    }
+}
+
+pub struct Playback {
+    replay: Replay,
+    cursor: usize,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        Playback { replay, cursor: 0 }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    pub fn generated_map(&self) -> bool {
+        self.replay.generated_map
+    }
+
+    pub fn rewind(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn next_command(&mut self, frame: u64) -> Option<Command> {
+        let input = self.replay.inputs.get(self.cursor)?;
+        if input.frame > frame {
+            return None;
+        }
+        self.cursor += 1;
+        Some(input.command)
+    }
+}
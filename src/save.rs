@@ -0,0 +1,87 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::world::World;
+
+pub const SAVE_VERSION: u32 = 13;
+pub const SAVE_PATH: &str = "sauvegarde.json";
+
+#[derive(Serialize)]
+struct SaveFileRef<'a> {
+    version: u32,
+    world: &'a World,
+}
+
+#[derive(Deserialize)]
+struct SaveFile {
+    world: World,
+}
+
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+pub fn save_world(world: &World, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+    let save = SaveFileRef { version: SAVE_VERSION, world };
+    let json = serde_json::to_string_pretty(&save)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+pub fn load_world(path: impl AsRef<Path>) -> Result<World, Box<dyn Error>> {
+    let json = fs::read_to_string(path)?;
+
+    // On lit d'abord la version seule pour refuser proprement un format inconnu
+    let header: SaveHeader = serde_json::from_str(&json)?;
+    if header.version != SAVE_VERSION {
+        return Err(format!(
+            "version de sauvegarde {} non supportée (attendue: {})",
+            header.version, SAVE_VERSION
+        ).into());
+    }
+
+    let mut save: SaveFile = serde_json::from_str(&json)?;
+    save.world.rebuild_caches();
+    Ok(save.world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Map;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let mut world = World::new(Map::new(800.0, 600.0), 42);
+        for _ in 0..120 {
+            world.update(1.0 / 60.0);
+        }
+        let path = temp_path("sauvegarde-aller-retour");
+        save_world(&world, &path).unwrap();
+        let loaded = load_world(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), serde_json::to_string(&world).unwrap());
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let world = World::new(Map::new(800.0, 600.0), 42);
+        let path = temp_path("sauvegarde-version");
+        let save = SaveFileRef { version: SAVE_VERSION + 1, world: &world };
+        fs::write(&path, serde_json::to_string(&save).unwrap()).unwrap();
+        let result = load_world(&path);
+        fs::remove_file(&path).unwrap();
+
+        let error = result.err().unwrap().to_string();
+        assert!(error.contains("non supportée"), "{}", error);
+    }
+}
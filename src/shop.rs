@@ -0,0 +1,87 @@
+use ggez::{Context, GameResult, graphics};
+use ggez::graphics::{Canvas, DrawParam, Color, Mesh, Text};
+use ggez::mint::Point2;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::Collider;
+use crate::items::ItemType;
+use crate::player::Player;
+use crate::weapon::WeaponKind;
+
+pub const MERCHANT_RADIUS: f32 = 14.0;
+// Distance à laquelle le héros peut commercer
+const TRADE_RANGE: f32 = 50.0;
+const MAX_HEALTH_CAP: i32 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Purchase {
+    Potion,
+    MaxHealth(i32),
+    Weapon(WeaponKind),
+}
+
+#[derive(Debug)]
+pub struct ShopOffer {
+    pub name: &'static str,
+    pub price: u32,
+    pub purchase: Purchase,
+}
+
+pub const CATALOGUE: &[ShopOffer] = &[
+    ShopOffer { name: "Potion", price: 3, purchase: Purchase::Potion },
+    ShopOffer { name: "Coeur (+20 PV max)", price: 8, purchase: Purchase::MaxHealth(20) },
+    ShopOffer { name: "Lance", price: 8, purchase: Purchase::Weapon(WeaponKind::Spear) },
+    ShopOffer { name: "Hache", price: 12, purchase: Purchase::Weapon(WeaponKind::Axe) },
+];
+
+impl ShopOffer {
+    // Vérifie que le héros peut payer et profiter de l'achat
+    pub fn check(&self, player: &Player) -> Result<(), &'static str> {
+        if player.coins < self.price {
+            return Err("pas assez de pièces");
+        }
+        match self.purchase {
+            Purchase::Potion if !player.inventory.can_add(ItemType::Potion) => Err("inventaire plein"),
+            Purchase::MaxHealth(_) if player.max_health >= MAX_HEALTH_CAP => Err("santé maximale déjà atteinte"),
+            Purchase::Weapon(weapon) if !player.inventory.can_add(ItemType::Weapon(weapon)) => Err("inventaire plein"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Merchant {
+    pub position: Point2<f32>,
+}
+
+impl Merchant {
+    pub fn new(position: Point2<f32>) -> Self {
+        Merchant { position }
+    }
+
+    pub fn is_in_reach(&self, player: &Collider) -> bool {
+        Collider::circle(self.position, TRADE_RANGE).intersects(player)
+    }
+
+    pub fn draw(&self, ctx: &mut Context, canvas: &mut Canvas) -> GameResult<()> {
+        let body = Mesh::new_circle(
+            ctx,
+            graphics::DrawMode::fill(),
+            Point2 { x: 0.0, y: 0.0 },
+            MERCHANT_RADIUS,
+            0.1,
+            Color::from_rgb(120, 60, 160),
+        )?;
+        canvas.draw(&body, DrawParam::default().dest(self.position));
+
+        let sign = Text::new("$");
+        canvas.draw(
+            &sign,
+            DrawParam::default()
+                .dest(Point2 { x: self.position.x - 4.0, y: self.position.y - 8.0 })
+                .color(Color::YELLOW),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use ggez::mint::Point2;
+
+use crate::collision::{Collider, Shape};
+
+// Assez grand pour qu'une entité ne couvre que quelques cellules
+pub const CELL_SIZE: f32 = 128.0;
+
+// Grille uniforme qui range des indices (dans le Vec qui possède les entités)
+// par cellule, pour ne tester que les voisins proches au lieu de tout parcourir
+#[derive(Debug, Clone, Default)]
+pub struct SpatialHash {
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn new() -> Self {
+        SpatialHash::default()
+    }
+
+    // Reconstruit l'index à partir des colliders, rangés dans l'ordre de leur Vec
+    pub fn rebuild(&mut self, colliders: impl IntoIterator<Item = Collider>) {
+        self.cells.clear();
+        for (index, collider) in colliders.into_iter().enumerate() {
+            self.insert(index, &collider);
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, collider: &Collider) {
+        for cell in cells_covering(collider) {
+            self.cells.entry(cell).or_default().push(index);
+        }
+    }
+
+    pub fn remove(&mut self, index: usize, collider: &Collider) {
+        for cell in cells_covering(collider) {
+            if let Some(indices) = self.cells.get_mut(&cell) {
+                indices.retain(|&other| other != index);
+            }
+        }
+    }
+
+    // Indices dont la cellule recoupe la zone, triés et sans doublon : l'ordre reste
+    // celui d'un parcours complet, ce qui garde les replays reproductibles.
+    // Ce sont des candidats : l'appelant teste encore l'intersection exacte.
+    pub fn query(&self, area: &Collider) -> Vec<usize> {
+        let mut found: Vec<usize> = cells_covering(area)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    pub fn query_radius(&self, center: Point2<f32>, radius: f32) -> Vec<usize> {
+        self.query(&Collider::circle(center, radius))
+    }
+
+    pub fn query_rect(&self, center: Point2<f32>, width: f32, height: f32) -> Vec<usize> {
+        self.query(&Collider::aabb(center, width, height))
+    }
+}
+
+fn cells_covering(collider: &Collider) -> impl Iterator<Item = (i32, i32)> {
+    let (half_width, half_height) = match collider.shape {
+        Shape::Circle { radius } => (radius, radius),
+        Shape::Aabb { half_width, half_height } => (half_width, half_height),
+    };
+    let first_col = cell_coordinate(collider.center.x - half_width);
+    let last_col = cell_coordinate(collider.center.x + half_width);
+    let first_row = cell_coordinate(collider.center.y - half_height);
+    let last_row = cell_coordinate(collider.center.y + half_height);
+
+    (first_col..=last_col).flat_map(move |col| (first_row..=last_row).map(move |row| (col, row)))
+}
+
+fn cell_coordinate(value: f32) -> i32 {
+    (value / CELL_SIZE).floor() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    use super::*;
+
+    fn point(x: f32, y: f32) -> Point2<f32> {
+        Point2 { x, y }
+    }
+
+    // Cercles et boîtes de toutes tailles, de part et d'autre de l'origine
+    fn colliders(seed: u64) -> Vec<Collider> {
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        (0..200)
+            .map(|_| {
+                let center = point(rng.gen_range(-600.0..600.0), rng.gen_range(-600.0..600.0));
+                if rng.gen_bool(0.5) {
+                    Collider::circle(center, rng.gen_range(2.0..200.0))
+                } else {
+                    Collider::aabb(center, rng.gen_range(4.0..300.0), rng.gen_range(4.0..300.0))
+                }
+            })
+            .collect()
+    }
+
+    fn brute_force(colliders: &[Collider], area: &Collider) -> Vec<usize> {
+        (0..colliders.len()).filter(|&index| colliders[index].intersects(area)).collect()
+    }
+
+    fn exact(colliders: &[Collider], area: &Collider, candidates: &[usize]) -> Vec<usize> {
+        candidates.iter().copied().filter(|&index| colliders[index].intersects(area)).collect()
+    }
+
+    fn assert_sorted_unique(indices: &[usize]) {
+        assert!(indices.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", indices);
+    }
+
+    #[test]
+    fn queries_match_a_full_scan() {
+        let colliders = colliders(3);
+        let mut hash = SpatialHash::new();
+        hash.rebuild(colliders.iter().copied());
+
+        let mut rng = ChaCha12Rng::seed_from_u64(4);
+        for _ in 0..100 {
+            let center = point(rng.gen_range(-700.0..700.0), rng.gen_range(-700.0..700.0));
+            let radius = rng.gen_range(1.0..250.0);
+            let (width, height) = (rng.gen_range(1.0..500.0), rng.gen_range(1.0..500.0));
+
+            let circle = Collider::circle(center, radius);
+            let found = hash.query_radius(center, radius);
+            assert_sorted_unique(&found);
+            assert_eq!(exact(&colliders, &circle, &found), brute_force(&colliders, &circle));
+
+            let rect = Collider::aabb(center, width, height);
+            let found = hash.query_rect(center, width, height);
+            assert_sorted_unique(&found);
+            assert_eq!(exact(&colliders, &rect, &found), brute_force(&colliders, &rect));
+        }
+    }
+
+    #[test]
+    fn collider_spanning_cells_is_reported_once() {
+        let mut hash = SpatialHash::new();
+        // À cheval sur quatre cellules autour de l'origine, et sur deux plus loin en négatif
+        hash.insert(0, &Collider::aabb(point(0.0, 0.0), 100.0, 100.0));
+        hash.insert(1, &Collider::circle(point(-CELL_SIZE * 2.0, -10.0), 30.0));
+
+        assert_eq!(hash.query_rect(point(0.0, 0.0), CELL_SIZE * 6.0, CELL_SIZE * 6.0), vec![0, 1]);
+        assert_eq!(hash.query_radius(point(-CELL_SIZE * 2.0 - 20.0, 0.0), 1.0), vec![1]);
+        assert_eq!(hash.query_radius(point(40.0, -40.0), 1.0), vec![0]);
+    }
+
+    #[test]
+    fn insert_and_remove_update_queries() {
+        let mut hash = SpatialHash::new();
+        let first = Collider::circle(point(-300.0, 50.0), 20.0);
+        let second = Collider::aabb(point(-290.0, 60.0), 40.0, 200.0);
+        hash.insert(3, &first);
+        hash.insert(7, &second);
+        assert_eq!(hash.query(&first), vec![3, 7]);
+
+        hash.remove(3, &first);
+        assert_eq!(hash.query(&first), vec![7]);
+        hash.remove(7, &second);
+        assert!(hash.query(&second).is_empty());
+    }
+
+    #[test]
+    fn rebuild_replaces_previous_contents() {
+        let mut hash = SpatialHash::new();
+        hash.insert(9, &Collider::circle(point(500.0, 500.0), 10.0));
+
+        hash.rebuild([Collider::circle(point(0.0, 0.0), 10.0), Collider::circle(point(500.0, 500.0), 10.0)]);
+        assert_eq!(hash.query_radius(point(500.0, 500.0), 5.0), vec![1]);
+        assert_eq!(hash.query_radius(point(0.0, 0.0), 5.0), vec![0]);
+    }
+}
@@ -0,0 +1,187 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use ggez::mint::Point2;
+use serde::Deserialize;
+
+use crate::map::{Map, ObstacleType, TileType, TILE_SIZE};
+
+pub const MAP_PATH: &str = "resources/maps/monde.tmj";
+
+// Bits de retournement que Tiled range dans le haut des identifiants de tuiles
+const GID_FLAGS: u32 = 0xF000_0000;
+
+#[derive(Deserialize)]
+struct TiledMap {
+    width: usize,
+    height: usize,
+    tilewidth: u32,
+    tileheight: u32,
+    layers: Vec<TiledLayer>,
+    #[serde(default)]
+    tilesets: Vec<TiledTilesetRef>,
+}
+
+#[derive(Deserialize)]
+struct TiledTilesetRef {
+    firstgid: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum TiledLayer {
+    #[serde(rename = "tilelayer")]
+    Tiles {
+        #[serde(default)]
+        data: Vec<u32>,
+    },
+    #[serde(rename = "objectgroup")]
+    Objects {
+        #[serde(default)]
+        objects: Vec<TiledObject>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct TiledObject {
+    // Tiled 1.9 a renommé `type` en `class`
+    #[serde(default, rename = "type", alias = "class")]
+    kind: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+}
+
+impl TiledObject {
+    fn center(&self) -> Point2<f32> {
+        Point2 {
+            x: self.x + self.width / 2.0,
+            y: self.y + self.height / 2.0,
+        }
+    }
+}
+
+pub fn load_map(path: impl AsRef<Path>) -> Result<Map, Box<dyn Error>> {
+    let json = fs::read_to_string(path)?;
+    parse_map(&json)
+}
+
+fn parse_map(json: &str) -> Result<Map, Box<dyn Error>> {
+    let tiled: TiledMap = serde_json::from_str(json)?;
+
+    if tiled.tilewidth as f32 != TILE_SIZE || tiled.tileheight as f32 != TILE_SIZE {
+        return Err(format!(
+            "tuiles de {}x{} non supportées (attendues: {}x{})",
+            tiled.tilewidth, tiled.tileheight, TILE_SIZE, TILE_SIZE
+        ).into());
+    }
+
+    let first_gid = tiled.tilesets.first().map_or(1, |tileset| tileset.firstgid);
+    let mut tile_types = vec![TileType::Grass; tiled.width * tiled.height];
+
+    // Les calques de tuiles s'empilent : une tuile non vide recouvre celles du dessous
+    for layer in &tiled.layers {
+        if let TiledLayer::Tiles { data } = layer {
+            for (tile_type, &gid) in tile_types.iter_mut().zip(data) {
+                let gid = gid & !GID_FLAGS;
+                if gid >= first_gid {
+                    *tile_type = tile_type_for(gid - first_gid);
+                }
+            }
+        }
+    }
+
+    let mut map = Map::from_rows(tiled.width, tiled.height, &tile_types);
+
+    for layer in &tiled.layers {
+        let TiledLayer::Objects { objects } = layer else {
+            continue;
+        };
+
+        for object in objects {
+            let center = object.center();
+            match object.kind.as_str() {
+                "rock" => map.add_obstacle(ObstacleType::Rock, center, object.width, object.height),
+                "tree" => map.add_obstacle(ObstacleType::Tree, center, object.width, object.height),
+                "bush" => map.add_obstacle(ObstacleType::Bush, center, object.width, object.height),
+                "player_spawn" => map.markers.player_spawn = Some(center),
+                "merchant" => map.markers.merchant = Some(center),
+                "boss_arena" => map.markers.boss_arena = Some(center),
+                "enemy_spawn" => map.markers.enemy_spawns.push(center),
+                "artifact" => map.markers.artifacts.push(center),
+                other => println!(" Objet de carte ignoré: \"{}\"", other),
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+// Chaque colonne du tileset correspond à un terrain (voir resources/tileset.png)
+fn tile_type_for(tile_id: u32) -> TileType {
+    match tile_id % 4 {
+        0 => TileType::Path,
+        1 => TileType::Grass,
+        2 => TileType::Water,
+        _ => TileType::Stone,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obstacle_count(map: &Map) -> usize {
+        serde_json::to_value(map).unwrap()["obstacles"].as_array().unwrap().len()
+    }
+
+    #[test]
+    fn loads_the_bundled_map() {
+        let map = load_map(MAP_PATH).unwrap();
+        assert_eq!((map.width, map.height), (40.0 * TILE_SIZE, 30.0 * TILE_SIZE));
+        assert_eq!(obstacle_count(&map), 71);
+        assert_eq!(map.markers.enemy_spawns.len(), 7);
+        assert_eq!(map.markers.artifacts.len(), 5);
+        assert!(map.markers.player_spawn.is_some());
+    }
+
+    #[test]
+    fn rejects_other_tile_sizes() {
+        let json = r#"{ "width": 1, "height": 1, "tilewidth": 32, "tileheight": 32, "layers": [] }"#;
+        let error = parse_map(json).err().unwrap().to_string();
+        assert!(error.contains("32x32"), "{}", error);
+    }
+
+    #[test]
+    fn masks_flip_bits_of_tile_ids() {
+        // Première tuile retournée horizontalement et verticalement : de l'eau quand même
+        let json = format!(
+            r#"{{ "width": 2, "height": 1, "tilewidth": 64, "tileheight": 64,
+                 "tilesets": [{{ "firstgid": 1 }}],
+                 "layers": [{{ "type": "tilelayer", "data": [{}, 1] }}] }}"#,
+            0xC000_0000u32 | 3
+        );
+        let map = parse_map(&json).unwrap();
+        assert_eq!(map.tile_at(Point2 { x: 32.0, y: 32.0 }), Some(TileType::Water));
+        assert_eq!(map.tile_at(Point2 { x: 96.0, y: 32.0 }), Some(TileType::Path));
+    }
+
+    #[test]
+    fn rectangle_objects_are_centred() {
+        let json = r#"{ "width": 4, "height": 4, "tilewidth": 64, "tileheight": 64,
+            "layers": [{ "type": "objectgroup", "objects": [
+                { "type": "rock", "x": 64, "y": 128, "width": 32, "height": 64 },
+                { "type": "player_spawn", "x": 10, "y": 20, "width": 40, "height": 60 }
+            ] }] }"#;
+        let map = parse_map(json).unwrap();
+        assert_eq!(map.markers.player_spawn, Some(Point2 { x: 30.0, y: 50.0 }));
+        assert!(!map.is_position_valid(Point2 { x: 80.0, y: 160.0 }, 1.0));
+        assert!(map.is_position_valid(Point2 { x: 80.0, y: 100.0 }, 1.0));
+    }
+}
@@ -2,21 +2,32 @@ use ggez::{Context, GameResult, graphics};
 use ggez::graphics::{Canvas, DrawParam, Color, Text, Mesh};
 use ggez::mint::Point2;
 
+use crate::boss::{Boss, BossPhase};
+use crate::camera::Camera;
+use crate::events::{MessageFeed, Statistics};
+use crate::inventory::{Inventory, HOTBAR_SIZE, INVENTORY_CAPACITY};
+use crate::player::{Player, StatChoice};
+use crate::shop::CATALOGUE;
+use crate::world::{GameState, World};
+
+const SLOT_SIZE: f32 = 44.0;
+const SLOT_GAP: f32 = 6.0;
+
 pub struct UI;
 
 impl UI {
     pub fn draw(
         ctx: &mut Context,
         canvas: &mut Canvas,
-        player_health: i32,
-        player_max_health: i32,
-        player_coins: u32,
-        player_weapon_level: u32,
-        quest_progress: (u32, u32),
-        game_time: f32,
-        game_state: &GameState,
+        world: &World,
+        camera: &Camera,
+        show_inventory: bool,
+        shop_open: bool,
     ) -> GameResult<()> {
         let (screen_width, _screen_height) = ctx.gfx.drawable_size();
+        let player = &world.player;
+        let player_health = player.health;
+        let player_max_health = player.max_health;
 
         let health_ratio = if player_max_health > 0 {
             (player_health as f32) / (player_max_health as f32)
@@ -46,26 +57,49 @@ impl UI {
         let hp_text = Text::new(format!("Nour: {}/{}", player_health, player_max_health));
         canvas.draw(&hp_text, DrawParam::default().dest(Point2 { x: 25.0, y: 22.0 }));
 
-        let coins_text = Text::new(format!("Pièces: {}", player_coins));
+        let coins_text = Text::new(format!("Pièces: {}", player.coins));
         canvas.draw(&coins_text, DrawParam::default().dest(Point2 { x: 25.0, y: 46.0 }));
 
-        let weapon_text = Text::new(format!("Arme: Niveau {}", player_weapon_level));
+        let weapon_text = Text::new(format!("Arme: {}", player.weapon_definition().name));
         canvas.draw(&weapon_text, DrawParam::default().dest(Point2 { x: 25.0, y: 68.0 }));
 
         // Quête et temps
-        let quest_text = Text::new(format!("Artefacts: {}/{}", quest_progress.0, quest_progress.1));
+        let quest_text = Text::new(format!("Artefacts: {}/{}", world.quest_items_collected, world.total_quest_items));
         canvas.draw(&quest_text, DrawParam::default().dest(Point2 { x: 25.0, y: 92.0 }));
 
-        let time_text = Text::new(format!("Temps: {:.1}s", game_time));
+        let time_text = Text::new(format!("Temps: {:.1}s", world.game_time));
         canvas.draw(&time_text, DrawParam::default().dest(Point2 { x: 25.0, y: 110.0 }));
 
+        Self::draw_xp_bar(ctx, canvas, player)?;
+
         // Contrôles
-        let controls_text = Text::new("Contrôles: ZQSD - Déplacement, Espace - Attaque");
-        let ctrl_x = (screen_width - 400.0).max(10.0);
+        let controls_text = Text::new("ZQSD - Déplacement, Espace - Attaque, 1-4 - Objets, I - Sac");
+        let ctrl_x = (screen_width - 520.0).max(10.0);
         canvas.draw(&controls_text, DrawParam::default().dest(Point2 { x: ctrl_x, y: 20.0 }));
 
+        Self::draw_hotbar(ctx, canvas, &player.inventory)?;
+
+        if let Some(boss) = &world.boss {
+            if boss.is_alive {
+                Self::draw_boss_bar(ctx, canvas, boss)?;
+            }
+        }
+
+        if show_inventory {
+            Self::draw_inventory(ctx, canvas, &player.inventory)?;
+        }
+
+        if shop_open {
+            Self::draw_shop(ctx, canvas, player)?;
+        } else if world.merchant.is_in_reach(&player.collider()) {
+            let prompt = Text::new("F - Parler au marchand");
+            let anchor = camera.world_to_screen(world.merchant.position);
+            let position = Point2 { x: anchor.x - 70.0, y: anchor.y - 40.0 };
+            canvas.draw(&prompt, DrawParam::default().dest(position));
+        }
+
         // Etats de jeu
-        match game_state {
+        match world.game_state {
             GameState::Playing => {}
             GameState::GameOver => {
                 let go = Text::new("GAME OVER - Appuyez sur R pour recommencer");
@@ -79,11 +113,207 @@ impl UI {
 
         Ok(())
     }
-}
 
-#[derive(Debug, PartialEq)]
-pub enum GameState {
-    Playing,
-    GameOver,
-    Victory,
-}
\ No newline at end of file
+    // Fil des derniers événements, en bas à gauche de l'écran
+    pub fn draw_messages(ctx: &mut Context, canvas: &mut Canvas, feed: &MessageFeed) {
+        let (_screen_width, screen_height) = ctx.gfx.drawable_size();
+        let messages: Vec<(&str, f32)> = feed.messages().collect();
+        let top = screen_height - 110.0 - messages.len() as f32 * 18.0;
+
+        for (index, (message, alpha)) in messages.into_iter().enumerate() {
+            let text = Text::new(message);
+            canvas.draw(
+                &text,
+                DrawParam::default()
+                    .dest(Point2 { x: 20.0, y: top + index as f32 * 18.0 })
+                    .color(Color::new(1.0, 1.0, 1.0, alpha)),
+            );
+        }
+    }
+
+    pub fn draw_statistics(canvas: &mut Canvas, statistics: &Statistics) {
+        let lines = [
+            format!("Ennemis vaincus: {}", statistics.enemies_killed),
+            format!("Dégâts infligés: {}", statistics.damage_dealt),
+            format!("Dégâts subis: {}", statistics.damage_taken),
+            format!("Pièces ramassées: {}", statistics.coins_collected),
+            format!("Objets ramassés: {}", statistics.items_collected),
+            format!("Potions bues: {}", statistics.potions_used),
+            format!("Achats: {}", statistics.purchases),
+        ];
+        for (index, line) in lines.iter().enumerate() {
+            let text = Text::new(line.as_str());
+            canvas.draw(&text, DrawParam::default().dest(Point2 { x: 200.0, y: 250.0 + index as f32 * 18.0 }));
+        }
+    }
+
+    fn draw_xp_bar(ctx: &mut Context, canvas: &mut Canvas, player: &Player) -> GameResult<()> {
+        let bar_width = 200.0;
+        let ratio = (player.xp as f32 / player.xp_to_next_level() as f32).clamp(0.0, 1.0);
+
+        let background = Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(20.0, 132.0, bar_width, 8.0),
+            Color::from_rgb(30, 30, 70),
+        )?;
+        let fill = Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(20.0, 132.0, bar_width * ratio, 8.0),
+            Color::from_rgb(80, 140, 255),
+        )?;
+        canvas.draw(&background, DrawParam::default());
+        canvas.draw(&fill, DrawParam::default());
+
+        let level_text = Text::new(format!("Niveau {} - XP {}/{}", player.level, player.xp, player.xp_to_next_level()));
+        canvas.draw(&level_text, DrawParam::default().dest(Point2 { x: 25.0, y: 144.0 }));
+
+        if player.pending_level_ups > 0 {
+            let (screen_width, _screen_height) = ctx.gfx.drawable_size();
+            let choices: Vec<String> = StatChoice::ALL.iter()
+                .enumerate()
+                .map(|(index, choice)| format!("{}: {}", index + 1, choice.label()))
+                .collect();
+            let prompt = Text::new(format!("NIVEAU SUPÉRIEUR ! {}", choices.join("   ")));
+            let x = (screen_width - 460.0).max(10.0) / 2.0;
+            canvas.draw(&prompt, DrawParam::default().dest(Point2 { x, y: 60.0 }).color(Color::YELLOW));
+        }
+
+        Ok(())
+    }
+
+    fn draw_slot(ctx: &mut Context, canvas: &mut Canvas, inventory: &Inventory, index: usize, x: f32, y: f32) -> GameResult<()> {
+        let frame = Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(x, y, SLOT_SIZE, SLOT_SIZE),
+            Color::from_rgba(30, 30, 30, 200),
+        )?;
+        canvas.draw(&frame, DrawParam::default());
+
+        if let Some(stack) = inventory.slot(index) {
+            let name = Text::new(stack.item_type.name());
+            canvas.draw(&name, DrawParam::default().dest(Point2 { x: x + 3.0, y: y + 4.0 }).scale([0.8, 0.8]));
+            let count = Text::new(format!("x{}", stack.count));
+            canvas.draw(&count, DrawParam::default().dest(Point2 { x: x + 3.0, y: y + 26.0 }));
+        }
+
+        Ok(())
+    }
+
+    fn draw_hotbar(ctx: &mut Context, canvas: &mut Canvas, inventory: &Inventory) -> GameResult<()> {
+        let (screen_width, screen_height) = ctx.gfx.drawable_size();
+        let total_width = HOTBAR_SIZE as f32 * (SLOT_SIZE + SLOT_GAP) - SLOT_GAP;
+        let start_x = (screen_width - total_width) / 2.0;
+        let y = screen_height - SLOT_SIZE - 10.0;
+
+        for index in 0..HOTBAR_SIZE {
+            let x = start_x + index as f32 * (SLOT_SIZE + SLOT_GAP);
+            Self::draw_slot(ctx, canvas, inventory, index, x, y)?;
+
+            let key = Text::new(format!("{}", index + 1));
+            canvas.draw(&key, DrawParam::default().dest(Point2 { x: x + SLOT_SIZE - 10.0, y: y - 16.0 }));
+        }
+
+        Ok(())
+    }
+
+    fn draw_inventory(ctx: &mut Context, canvas: &mut Canvas, inventory: &Inventory) -> GameResult<()> {
+        let (screen_width, screen_height) = ctx.gfx.drawable_size();
+        let columns = HOTBAR_SIZE;
+        let rows = INVENTORY_CAPACITY.div_ceil(columns);
+        let panel_width = columns as f32 * (SLOT_SIZE + SLOT_GAP) + 30.0;
+        let panel_height = rows as f32 * (SLOT_SIZE + SLOT_GAP) + 60.0;
+        let panel_x = (screen_width - panel_width) / 2.0;
+        let panel_y = (screen_height - panel_height) / 2.0;
+
+        let panel = Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(panel_x, panel_y, panel_width, panel_height),
+            Color::from_rgba(0, 0, 0, 220),
+        )?;
+        canvas.draw(&panel, DrawParam::default());
+
+        let title = Text::new(format!("Inventaire ({}/{})", inventory.used_slots(), INVENTORY_CAPACITY));
+        canvas.draw(&title, DrawParam::default().dest(Point2 { x: panel_x + 15.0, y: panel_y + 12.0 }));
+
+        for index in 0..INVENTORY_CAPACITY {
+            let x = panel_x + 15.0 + (index % columns) as f32 * (SLOT_SIZE + SLOT_GAP);
+            let y = panel_y + 40.0 + (index / columns) as f32 * (SLOT_SIZE + SLOT_GAP);
+            Self::draw_slot(ctx, canvas, inventory, index, x, y)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_shop(ctx: &mut Context, canvas: &mut Canvas, player: &Player) -> GameResult<()> {
+        let (screen_width, _screen_height) = ctx.gfx.drawable_size();
+        let panel_width = 340.0;
+        let panel_height = 60.0 + CATALOGUE.len() as f32 * 24.0;
+        let panel_x = (screen_width - panel_width) / 2.0;
+        let panel_y = 140.0;
+
+        let panel = Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(panel_x, panel_y, panel_width, panel_height),
+            Color::from_rgba(20, 10, 30, 230),
+        )?;
+        canvas.draw(&panel, DrawParam::default());
+
+        let title = Text::new(format!("Marchand - {} pièces (F pour fermer)", player.coins));
+        canvas.draw(&title, DrawParam::default().dest(Point2 { x: panel_x + 12.0, y: panel_y + 12.0 }));
+
+        for (index, offer) in CATALOGUE.iter().enumerate() {
+            // Les offres inaccessibles sont grisées
+            let color = if offer.check(player).is_ok() { Color::WHITE } else { Color::from_rgb(120, 120, 120) };
+            let line = Text::new(format!("{} - {} ({} pièces)", index + 1, offer.name, offer.price));
+            canvas.draw(
+                &line,
+                DrawParam::default()
+                    .dest(Point2 { x: panel_x + 12.0, y: panel_y + 40.0 + index as f32 * 24.0 })
+                    .color(color),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn draw_boss_bar(ctx: &mut Context, canvas: &mut Canvas, boss: &Boss) -> GameResult<()> {
+        let (screen_width, screen_height) = ctx.gfx.drawable_size();
+        let bar_width = screen_width * 0.6;
+        let bar_x = (screen_width - bar_width) / 2.0;
+        // Au-dessus de la barre d'accès rapide
+        let bar_y = screen_height - SLOT_SIZE - 50.0;
+
+        if boss.phase == BossPhase::Intro {
+            let intro = Text::new("LE DRAGON ANCIEN S'ÉVEILLE...");
+            canvas.draw(&intro, DrawParam::default().dest(Point2 { x: bar_x, y: 180.0 }));
+        }
+
+        let ratio = (boss.health as f32 / boss.max_health as f32).clamp(0.0, 1.0);
+
+        let background = Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(bar_x, bar_y, bar_width, 14.0),
+            Color::from_rgb(60, 0, 0),
+        )?;
+        let fill = Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(bar_x, bar_y, bar_width * ratio, 14.0),
+            Color::from_rgb(200, 30, 30),
+        )?;
+
+        canvas.draw(&background, DrawParam::default());
+        canvas.draw(&fill, DrawParam::default());
+
+        let title = Text::new(format!("Dragon Ancien - {}", boss.phase.label()));
+        canvas.draw(&title, DrawParam::default().dest(Point2 { x: bar_x, y: bar_y - 18.0 }));
+
+        Ok(())
+    }
+}
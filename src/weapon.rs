@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::projectile::ProjectileKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WeaponKind {
+    Sword,
+    Spear,
+    Axe,
+    Bow,
+    Staff,
+}
+
+pub struct WeaponDefinition {
+    pub name: &'static str,
+    pub damage: i32,
+    // Portée du coup devant le héros
+    pub reach: f32,
+    // Largeur balayée par le coup
+    pub arc: f32,
+    pub cooldown: f32,
+    pub knockback: f32,
+    // Les armes à distance tirent ce projectile au lieu de frapper
+    pub projectile: Option<ProjectileKind>,
+}
+
+const SWORD: WeaponDefinition = WeaponDefinition {
+    name: "Épée",
+    damage: 10,
+    reach: 40.0,
+    arc: 56.0,
+    cooldown: 0.4,
+    knockback: 240.0,
+    projectile: None,
+};
+
+const SPEAR: WeaponDefinition = WeaponDefinition {
+    name: "Lance",
+    damage: 12,
+    reach: 70.0,
+    arc: 24.0,
+    cooldown: 0.55,
+    knockback: 300.0,
+    projectile: None,
+};
+
+const AXE: WeaponDefinition = WeaponDefinition {
+    name: "Hache",
+    damage: 22,
+    reach: 36.0,
+    arc: 80.0,
+    cooldown: 0.9,
+    knockback: 380.0,
+    projectile: None,
+};
+
+const BOW: WeaponDefinition = WeaponDefinition {
+    name: "Arc long",
+    damage: 14,
+    reach: 0.0,
+    arc: 0.0,
+    cooldown: 0.7,
+    knockback: 160.0,
+    projectile: Some(ProjectileKind::Arrow),
+};
+
+const STAFF: WeaponDefinition = WeaponDefinition {
+    name: "Bâton",
+    damage: 18,
+    reach: 0.0,
+    arc: 0.0,
+    cooldown: 1.1,
+    knockback: 260.0,
+    projectile: Some(ProjectileKind::Fireball),
+};
+
+impl WeaponKind {
+    pub const ALL: [WeaponKind; 5] = [
+        WeaponKind::Sword,
+        WeaponKind::Spear,
+        WeaponKind::Axe,
+        WeaponKind::Bow,
+        WeaponKind::Staff,
+    ];
+
+    pub fn definition(self) -> &'static WeaponDefinition {
+        match self {
+            WeaponKind::Sword => &SWORD,
+            WeaponKind::Spear => &SPEAR,
+            WeaponKind::Axe => &AXE,
+            WeaponKind::Bow => &BOW,
+            WeaponKind::Staff => &STAFF,
+        }
+    }
+}
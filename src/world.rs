@@ -0,0 +1,822 @@
+use ggez::mint::Point2;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::boss::{Boss, BossAction, BOSS_RADIUS};
+use crate::player::{Player, Direction, StatChoice};
+use crate::enemy::{Enemy, EnemyAttack, EnemyType, ENEMY_RADIUS};
+use crate::events::{DamageCause, DamageSource, DamageTarget, GameEvent};
+use crate::items::{Item, ItemType};
+use crate::map::{Map, TILE_SIZE};
+use crate::pathfinding::NavGrid;
+use crate::projectile::{Projectile, ProjectileOwner};
+use crate::collision::Collider;
+use crate::shop::{Merchant, Purchase, CATALOGUE, MERCHANT_RADIUS};
+use crate::spatial::SpatialHash;
+use crate::weapon::WeaponKind;
+
+const POTION_HEAL: i32 = 25;
+const BOMB_RADIUS: f32 = 90.0;
+const BOMB_DAMAGE: i32 = 40;
+const BOMB_KNOCKBACK: f32 = 420.0;
+// Densité des objets posés au lancement, pour que les grandes cartes en aient assez
+const TILES_PER_ITEM: f32 = 8.0;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum GameState {
+    Playing,
+    GameOver,
+    Victory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Command {
+    Move(Direction),
+    StopMovement,
+    Attack,
+    // Utilise l'objet de l'emplacement donné de la barre d'accès rapide
+    UseItem(usize),
+    // Achète l'offre donnée du catalogue du marchand
+    Buy(usize),
+    ChooseStat(StatChoice),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct World {
+    pub player: Player,
+    pub enemies: Vec<Enemy>,
+    pub items: Vec<Item>,
+    pub projectiles: Vec<Projectile>,
+    pub map: Map,
+    pub merchant: Merchant,
+    #[serde(skip)]
+    nav: NavGrid,
+    #[serde(skip)]
+    enemy_index: SpatialHash,
+    #[serde(skip)]
+    item_index: SpatialHash,
+    #[serde(skip)]
+    projectile_index: SpatialHash,
+    // Vidé par le jeu après chaque frame (voir Game::dispatch_events)
+    #[serde(skip)]
+    events: Vec<GameEvent>,
+    pub game_state: GameState,
+    spawn_timer: f32,
+    pub quest_items_collected: u32,
+    pub total_quest_items: u32,
+    pub boss: Option<Boss>,
+    pub game_time: f32,
+    pub frame: u64,
+    pub seed: u64,
+    rng: ChaCha12Rng,
+    next_entity_id: u32,
+}
+
+impl World {
+    pub fn new(map: Map, seed: u64) -> Self {
+        let nav = NavGrid::build(&map, ENEMY_RADIUS);
+        let start = map.markers.player_spawn
+            .unwrap_or(Point2 { x: map.width / 2.0, y: map.height / 2.0 });
+        let merchant = Merchant::new(map.markers.merchant
+            .unwrap_or_else(|| find_merchant_spot(&map, start)));
+
+        let mut world = World {
+            player: Player::new(start.x, start.y),
+            enemies: Vec::new(),
+            items: Vec::new(),
+            projectiles: Vec::new(),
+            map,
+            merchant,
+            nav,
+            enemy_index: SpatialHash::new(),
+            item_index: SpatialHash::new(),
+            projectile_index: SpatialHash::new(),
+            events: Vec::new(),
+            game_state: GameState::Playing,
+            spawn_timer: 2.0,
+            quest_items_collected: 0,
+            total_quest_items: 5,
+            boss: None,
+            game_time: 0.0,
+            frame: 0,
+            seed,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            next_entity_id: 0,
+        };
+
+        world.spawn_initial_items();
+        world.spawn_quest_items();
+        world.reindex_items();
+        world.events.push(GameEvent::GameStarted { seed, artifacts: world.total_quest_items });
+
+        world
+    }
+
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    // La grille de navigation et les index spatiaux ne sont pas sauvegardés,
+    // on les reconstruit après un chargement
+    pub fn rebuild_caches(&mut self) {
+        self.map.rebuild_obstacle_index();
+        self.nav = NavGrid::build(&self.map, ENEMY_RADIUS);
+        self.reindex_enemies();
+        self.reindex_items();
+    }
+
+    fn reindex_enemies(&mut self) {
+        self.enemy_index.rebuild(self.enemies.iter().map(Enemy::collider));
+    }
+
+    fn reindex_items(&mut self) {
+        self.item_index.rebuild(self.items.iter().map(Item::collider));
+    }
+
+    // Ennemis et objets dans une zone rectangulaire, pour ne dessiner que ce qui est à l'écran
+    pub fn enemies_in(&self, center: Point2<f32>, width: f32, height: f32) -> impl Iterator<Item = &Enemy> {
+        self.enemy_index.query_rect(center, width, height).into_iter().map(|index| &self.enemies[index])
+    }
+
+    pub fn items_in(&self, center: Point2<f32>, width: f32, height: f32) -> impl Iterator<Item = &Item> {
+        self.item_index.query_rect(center, width, height).into_iter().map(|index| &self.items[index])
+    }
+
+    fn push_enemy(&mut self, position: Point2<f32>, enemy_type: EnemyType) {
+        let id = self.allocate_entity_id();
+        let enemy = Enemy::new(id, position.x, position.y, enemy_type);
+        self.enemy_index.insert(self.enemies.len(), &enemy.collider());
+        self.enemies.push(enemy);
+        self.events.push(GameEvent::EnemySpawned(enemy_type));
+    }
+
+    fn allocate_entity_id(&mut self) -> u32 {
+        self.next_entity_id += 1;
+        self.next_entity_id
+    }
+
+    pub fn apply(&mut self, command: Command) {
+        match command {
+            Command::Move(direction) => self.player.move_direction(direction),
+            Command::StopMovement => self.player.stop_movement(),
+            Command::Attack => {
+                if let Some(projectile) = self.player.attack() {
+                    self.projectiles.push(projectile);
+                }
+            }
+            Command::UseItem(slot) => self.use_item(slot),
+            Command::Buy(index) => self.buy(index),
+            Command::ChooseStat(choice) => {
+                if self.player.choose_stat(choice) {
+                    self.events.push(GameEvent::StatChosen(choice));
+                }
+            }
+        }
+    }
+
+    fn buy(&mut self, index: usize) {
+        if self.game_state != GameState::Playing || !self.merchant.is_in_reach(&self.player.collider()) {
+            return;
+        }
+        let Some(offer) = CATALOGUE.get(index) else {
+            return;
+        };
+
+        if let Err(reason) = offer.check(&self.player) {
+            self.events.push(GameEvent::PurchaseRefused { offer: offer.name, reason });
+            return;
+        }
+
+        self.player.coins -= offer.price;
+        match offer.purchase {
+            Purchase::Potion => {
+                self.player.inventory.add(ItemType::Potion);
+            }
+            Purchase::MaxHealth(amount) => self.player.increase_max_health(amount),
+            Purchase::Weapon(weapon) => {
+                self.player.inventory.add(ItemType::Weapon(weapon));
+            }
+        }
+        self.events.push(GameEvent::Purchased { offer: offer.name, price: offer.price, coins_left: self.player.coins });
+    }
+
+    fn use_item(&mut self, slot: usize) {
+        if self.game_state != GameState::Playing {
+            return;
+        }
+        let Some(stack) = self.player.inventory.slot(slot) else {
+            return;
+        };
+
+        let consumed = match stack.item_type {
+            ItemType::Potion => {
+                if self.player.health >= self.player.max_health {
+                    self.events.push(GameEvent::ItemKept(ItemType::Potion));
+                    false
+                } else {
+                    let old_health = self.player.health;
+                    self.player.heal(POTION_HEAL);
+                    self.events.push(GameEvent::Healed { from: old_health, to: self.player.health });
+                    true
+                }
+            }
+            ItemType::Bomb => {
+                self.explode_bomb();
+                true
+            }
+            ItemType::Key => {
+                self.events.push(GameEvent::ItemKept(ItemType::Key));
+                false
+            }
+            ItemType::Weapon(weapon) => {
+                // L'arme rangée prend la place de celle qu'on équipe
+                self.player.inventory.remove_one(slot);
+                let previous = self.player.equip(weapon);
+                self.player.inventory.add(ItemType::Weapon(previous));
+                self.events.push(GameEvent::WeaponEquipped { weapon, previous });
+                false
+            }
+            _ => false,
+        };
+
+        if consumed {
+            self.player.inventory.remove_one(slot);
+        }
+    }
+
+    fn explode_bomb(&mut self) {
+        let blast = Collider::circle(self.player.position, BOMB_RADIUS);
+        let mut enemies_hit = 0;
+
+        for index in self.enemy_index.query(&blast) {
+            let enemy = &mut self.enemies[index];
+            if enemy.is_alive && blast.intersects(&enemy.collider()) {
+                let health_before = enemy.health;
+                enemy.take_damage(BOMB_DAMAGE, self.player.position, BOMB_KNOCKBACK);
+                enemies_hit += 1;
+                self.events.push(GameEvent::DamageDealt {
+                    target: DamageTarget::Enemy(enemy.enemy_type),
+                    cause: DamageCause::Bomb,
+                    amount: BOMB_DAMAGE,
+                    health_before,
+                    health_after: enemy.health,
+                });
+            }
+        }
+
+        if let Some(boss) = &mut self.boss {
+            if boss.is_vulnerable() && blast.intersects(&boss.collider()) {
+                let health_before = boss.health;
+                boss.take_damage(BOMB_DAMAGE);
+                enemies_hit += 1;
+                self.events.push(GameEvent::DamageDealt {
+                    target: DamageTarget::Boss,
+                    cause: DamageCause::Bomb,
+                    amount: BOMB_DAMAGE,
+                    health_before,
+                    health_after: boss.health,
+                });
+            }
+        }
+
+        self.events.push(GameEvent::BombExploded { enemies_hit });
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if self.game_state != GameState::Playing {
+            return;
+        }
+
+        self.frame += 1;
+        self.game_time += dt;
+        self.player.update(dt, &self.map);
+
+        let player_collider = self.player.collider();
+        for enemy in &mut self.enemies {
+            enemy.update(dt, &player_collider, &self.map, &self.nav);
+        }
+        self.reindex_enemies();
+
+        self.update_boss(dt);
+        self.update_projectiles(dt);
+
+        self.spawn_timer -= dt;
+        if self.spawn_timer <= 0.0 && self.enemies.len() < 8 {
+            self.spawn_enemy();
+            self.spawn_timer = 3.0;
+        }
+
+        self.check_collisions();
+
+        // Un seul contrôle par frame, quelle que soit l'origine du coup fatal
+        if !self.player.is_alive() {
+            self.game_state = GameState::GameOver;
+            self.events.push(GameEvent::PlayerDied);
+        }
+
+        self.check_quest_progress();
+    }
+
+    fn spawn_initial_items(&mut self) {
+        let tiles = (self.map.width / TILE_SIZE) * (self.map.height / TILE_SIZE);
+        let count = (tiles / TILES_PER_ITEM).round().max(1.0) as usize;
+        let rng = &mut self.rng;
+        
+        for _ in 0..count {
+            // Pas de clés tant qu'aucune serrure ne les utilise
+            let item_type = match rng.gen_range(0..9) {
+                0..=3 => ItemType::Coin,
+                4..=5 => ItemType::Potion,
+                6 => ItemType::Bomb,
+                _ => {
+                    let index = rng.gen_range(0..WeaponKind::ALL.len());
+                    ItemType::Weapon(WeaponKind::ALL[index])
+                }
+            };
+            
+            let mut position;
+            let mut attempts = 0;
+            
+            loop {
+                position = random_point(rng, &self.map);
+                
+                if self.map.can_spawn_at(position, 10.0) && attempts < 50 {
+                    break;
+                }
+                attempts += 1;
+                
+                if attempts >= 50 {
+                    position = self.map.center();
+                    break;
+                }
+            }
+            
+            self.items.push(Item::new(position.x, position.y, item_type));
+        }
+    }
+
+    fn spawn_quest_items(&mut self) {
+        // Une carte qui place ses artefacts fixe aussi leur nombre
+        if !self.map.markers.artifacts.is_empty() {
+            self.total_quest_items = self.map.markers.artifacts.len() as u32;
+            for position in &self.map.markers.artifacts {
+                self.items.push(Item::new_quest(position.x, position.y));
+            }
+            return;
+        }
+
+        let rng = &mut self.rng;
+        
+        for i in 0..self.total_quest_items {
+            let mut position;
+            let mut attempts = 0;
+            
+            loop {
+                position = random_point(rng, &self.map);
+                
+                if self.map.can_spawn_at(position, 15.0) && attempts < 50 {
+                    break;
+                }
+                attempts += 1;
+                
+                if attempts >= 50 {
+                
+                    let angle = (i as f32 / self.total_quest_items as f32) * 2.0 * std::f32::consts::PI;
+                    position = Point2 {
+                        x: self.map.center().x + angle.cos() * 200.0,
+                        y: self.map.center().y + angle.sin() * 150.0,
+                    };
+                    break;
+                }
+            }
+            
+         
+            self.items.push(Item::new_quest(position.x, position.y));
+        }
+    }
+
+    fn spawn_enemy(&mut self) {
+        let enemy_type = match self.rng.gen_range(0..10) {
+            0..=4 => EnemyType::Goblin,
+            5 => EnemyType::Archer,
+            6..=8 => EnemyType::Orc,
+            _ => EnemyType::Dragon,
+        };
+
+        // Les points d'apparition de la carte, sinon un cercle autour du héros
+        if !self.map.markers.enemy_spawns.is_empty() {
+            let index = self.rng.gen_range(0..self.map.markers.enemy_spawns.len());
+            let position = self.map.markers.enemy_spawns[index];
+            self.push_enemy(position, enemy_type);
+            return;
+        }
+
+        let mut position;
+        let mut attempts = 0;
+        
+        loop {
+         
+            let angle = self.rng.gen_range(0.0..std::f32::consts::TAU);
+            let distance = self.rng.gen_range(150.0..300.0);
+            position = Point2 {
+                x: self.player.position.x + angle.cos() * distance,
+                y: self.player.position.y + angle.sin() * distance,
+            };
+            
+            if self.map.can_spawn_at(position, 12.0) &&
+               position.x >= 50.0 && position.x <= self.map.width - 50.0 &&
+               position.y >= 50.0 && position.y <= self.map.height - 50.0 &&
+               attempts < 30 {
+                break;
+            }
+            attempts += 1;
+            
+            if attempts >= 30 {
+               
+                position = random_point(&mut self.rng, &self.map);
+                break;
+            }
+        }
+
+        self.push_enemy(position, enemy_type);
+    }
+
+    fn spawn_boss(&mut self) {
+        if self.boss.is_none() {
+         
+            let boss_position = self.map.markers.boss_arena
+                .unwrap_or(Point2 { x: self.map.width / 2.0, y: 100.0 });
+            let id = self.allocate_entity_id();
+            self.boss = Some(Boss::new(id, boss_position.x, boss_position.y));
+            self.events.push(GameEvent::BossSpawned);
+        }
+    }
+
+    fn update_boss(&mut self, dt: f32) {
+        let Some(boss) = &mut self.boss else {
+            return;
+        };
+
+        let actions = boss.update(dt, &self.player.collider(), &self.map);
+        let boss_position = boss.position;
+
+        for action in actions {
+            match action {
+                BossAction::Hit { damage, attack } => {
+                    let old_health = self.player.health;
+                    if !self.player.take_damage(damage, boss_position) {
+                        continue;
+                    }
+                    self.events.push(GameEvent::PlayerDamaged {
+                        source: DamageSource::Boss(attack),
+                        amount: damage,
+                        health_before: old_health,
+                        health_after: self.player.health,
+                    });
+                }
+                BossAction::Summon(enemy_type, count) => {
+                    self.events.push(GameEvent::BossSummoned);
+                    for _ in 0..count {
+                        self.spawn_add(boss_position, enemy_type);
+                    }
+                }
+                BossAction::Shoot(projectile) => {
+                    self.projectiles.push(projectile);
+                }
+                BossAction::PhaseChanged(phase) => {
+                    self.events.push(GameEvent::BossPhaseChanged(phase));
+                }
+            }
+        }
+    }
+
+    fn update_projectiles(&mut self, dt: f32) {
+        for projectile in &mut self.projectiles {
+            projectile.update(dt, &self.map);
+        }
+        self.projectile_index.rebuild(self.projectiles.iter().map(Projectile::collider));
+
+        // Seuls les projectiles proches du héros peuvent le toucher
+        let player_collider = self.player.collider();
+        for index in self.projectile_index.query(&player_collider) {
+            let projectile = &mut self.projectiles[index];
+            if !projectile.is_active || projectile.owner != ProjectileOwner::Enemy ||
+               !projectile.collider().intersects(&player_collider) {
+                continue;
+            }
+
+            projectile.is_active = false;
+            let old_health = self.player.health;
+            // Le recul part de l'arrière du projectile
+            let from = Point2 {
+                x: projectile.position.x - projectile.velocity.x,
+                y: projectile.position.y - projectile.velocity.y,
+            };
+            if self.player.take_damage(projectile.damage, from) {
+                self.events.push(GameEvent::PlayerDamaged {
+                    source: DamageSource::Projectile(projectile.kind),
+                    amount: projectile.damage,
+                    health_before: old_health,
+                    health_after: self.player.health,
+                });
+            }
+        }
+
+        for projectile in &mut self.projectiles {
+            if !projectile.is_active || projectile.owner != ProjectileOwner::Player {
+                continue;
+            }
+
+            let collider = projectile.collider();
+            let target = self.enemy_index.query(&collider).into_iter()
+                .find(|&index| self.enemies[index].is_alive && collider.intersects(&self.enemies[index].collider()));
+
+            if let Some(index) = target {
+                let enemy = &mut self.enemies[index];
+                let health_before = enemy.health;
+                projectile.is_active = false;
+                enemy.take_damage(projectile.damage, projectile.position, projectile.knockback);
+                self.events.push(GameEvent::DamageDealt {
+                    target: DamageTarget::Enemy(enemy.enemy_type),
+                    cause: DamageCause::Projectile(projectile.kind),
+                    amount: projectile.damage,
+                    health_before,
+                    health_after: enemy.health,
+                });
+            } else if let Some(boss) = &mut self.boss {
+                if boss.is_vulnerable() && collider.intersects(&boss.collider()) {
+                    let health_before = boss.health;
+                    projectile.is_active = false;
+                    boss.take_damage(projectile.damage);
+                    self.events.push(GameEvent::DamageDealt {
+                        target: DamageTarget::Boss,
+                        cause: DamageCause::Projectile(projectile.kind),
+                        amount: projectile.damage,
+                        health_before,
+                        health_after: boss.health,
+                    });
+                }
+            }
+        }
+
+        self.projectiles.retain(|projectile| projectile.is_active);
+    }
+
+    fn spawn_add(&mut self, around: Point2<f32>, enemy_type: EnemyType) {
+        for _ in 0..20 {
+            let angle = self.rng.gen_range(0.0..std::f32::consts::TAU);
+            let distance = self.rng.gen_range(BOSS_RADIUS + 30.0..BOSS_RADIUS + 90.0);
+            let position = Point2 {
+                x: around.x + angle.cos() * distance,
+                y: around.y + angle.sin() * distance,
+            };
+
+            if self.map.can_spawn_at(position, ENEMY_RADIUS) {
+                self.push_enemy(position, enemy_type);
+                return;
+            }
+        }
+    }
+
+    fn check_collisions(&mut self) {
+ 
+        let player_collider = self.player.collider();
+        
+        for index in self.item_index.query(&player_collider) {
+            let item = &mut self.items[index];
+            if !item.is_collected && item.is_colliding_with_player(&player_collider) {
+                // Inventaire plein : l'objet reste au sol
+                if item.item_type.is_carried() {
+                    if self.player.inventory.add(item.item_type) {
+                        item.collect();
+                        self.events.push(GameEvent::ItemCollected(item.item_type));
+                    }
+                    continue;
+                }
+
+                match item.collect() {
+                    ItemType::Coin => {
+                        self.player.add_coin();
+                        self.events.push(GameEvent::ItemCollected(ItemType::Coin));
+                    }
+                    ItemType::QuestArtifact => {
+                        self.quest_items_collected += 1;
+                        self.events.push(GameEvent::ArtifactCollected {
+                            collected: self.quest_items_collected,
+                            total: self.total_quest_items,
+                        });
+                    }
+                    ItemType::Potion | ItemType::Key | ItemType::Bomb | ItemType::Weapon(_) => {}
+                }
+            }
+        }
+
+        let items_before = self.items.len();
+        self.items.retain(|item| !item.is_collected);
+        if self.items.len() != items_before {
+            self.reindex_items();
+        }
+
+      
+        for enemy in &mut self.enemies {
+            if enemy.is_alive && enemy.is_in_attack_range(&player_collider) && enemy.can_attack() {
+                match enemy.attack(self.player.position) {
+                    EnemyAttack::Melee(damage) => {
+                        let old_health = self.player.health;
+                        if !self.player.take_damage(damage, enemy.position) {
+                            continue;
+                        }
+
+                        self.events.push(GameEvent::PlayerDamaged {
+                            source: DamageSource::Enemy(enemy.enemy_type),
+                            amount: damage,
+                            health_before: old_health,
+                            health_after: self.player.health,
+                        });
+                    }
+                    EnemyAttack::Ranged(projectile) => {
+                        self.projectiles.push(projectile);
+                    }
+                }
+            }
+        }
+
+        if self.player.is_attacking {
+            let mut enemies_hit = 0;
+            let hitbox = self.player.swing_hitbox();
+            
+            for index in self.enemy_index.query(&hitbox) {
+                let enemy = &mut self.enemies[index];
+                if enemy.is_alive && hitbox.intersects(&enemy.collider()) &&
+                   self.player.register_swing_hit(enemy.id) {
+                    let damage = self.player.get_attack_damage();
+                    let old_health = enemy.health;
+                    enemy.take_damage(damage, self.player.position, self.player.weapon_definition().knockback);
+                    enemies_hit += 1;
+                    self.events.push(GameEvent::DamageDealt {
+                        target: DamageTarget::Enemy(enemy.enemy_type),
+                        cause: DamageCause::Melee,
+                        amount: damage,
+                        health_before: old_health,
+                        health_after: enemy.health,
+                    });
+                }
+            }
+
+            if let Some(boss) = &mut self.boss {
+                if boss.is_vulnerable() && hitbox.intersects(&boss.collider()) &&
+                   self.player.register_swing_hit(boss.id) {
+                    let damage = self.player.get_attack_damage();
+                    let old_health = boss.health;
+                    boss.take_damage(damage);
+                    enemies_hit += 1;
+                    self.events.push(GameEvent::DamageDealt {
+                        target: DamageTarget::Boss,
+                        cause: DamageCause::Melee,
+                        amount: damage,
+                        health_before: old_health,
+                        health_after: boss.health,
+                    });
+                }
+            }
+            
+            if enemies_hit > 0 {
+                self.events.push(GameEvent::AttackLanded { hits: enemies_hit });
+            }
+        }
+
+        let mut xp = 0;
+        for enemy in self.enemies.iter().filter(|enemy| !enemy.is_alive) {
+            self.events.push(GameEvent::EnemyKilled(enemy.enemy_type));
+            xp += enemy.enemy_type.profile().xp_reward;
+        }
+        if xp > 0 {
+            self.award_xp(xp);
+        }
+
+        let enemies_before = self.enemies.len();
+        self.enemies.retain(|enemy| enemy.is_alive);
+        if self.enemies.len() != enemies_before {
+            self.reindex_enemies();
+        }
+    }
+
+    fn award_xp(&mut self, amount: u32) {
+        let levels = self.player.gain_xp(amount);
+        self.events.push(GameEvent::XpGained {
+            amount,
+            xp: self.player.xp,
+            next_level: self.player.xp_to_next_level(),
+        });
+        if levels > 0 {
+            self.events.push(GameEvent::LevelUp { level: self.player.level });
+        }
+    }
+
+    fn check_quest_progress(&mut self) {
+     
+        if self.quest_items_collected >= self.total_quest_items && self.boss.is_none() {
+            self.spawn_boss();
+        }
+    
+        if let Some(boss) = &self.boss {
+            if !boss.is_alive {
+                self.game_state = GameState::Victory;
+                self.events.push(GameEvent::BossDefeated);
+                self.events.push(GameEvent::Victory { time: self.game_time, coins: self.player.coins });
+            }
+        }
+    }
+}
+
+// Premier emplacement libre autour du point de départ du héros
+fn find_merchant_spot(map: &Map, start: Point2<f32>) -> Point2<f32> {
+    for ring in 1..8 {
+        let distance = ring as f32 * 40.0;
+        for step in 0..8 {
+            let angle = step as f32 * std::f32::consts::FRAC_PI_4;
+            let position = Point2 {
+                x: start.x + angle.cos() * distance,
+                y: start.y + angle.sin() * distance,
+            };
+            if map.can_spawn_at(position, MERCHANT_RADIUS) {
+                return position;
+            }
+        }
+    }
+    start
+}
+
+// Point au hasard, à distance des bords de la carte
+fn random_point(rng: &mut ChaCha12Rng, map: &Map) -> Point2<f32> {
+    let margin_x = 100.0_f32.min(map.width / 4.0);
+    let margin_y = 100.0_f32.min(map.height / 4.0);
+    Point2 {
+        x: rng.gen_range(margin_x..map.width - margin_x),
+        y: rng.gen_range(margin_y..map.height - margin_y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::PLAYER_RADIUS;
+
+    // Même graine, mêmes commandes : la simulation doit être identique
+    fn simulate(seed: u64) -> String {
+        let mut world = World::new(Map::new(800.0, 600.0), seed);
+        let directions = [Direction::Right, Direction::Down, Direction::Left, Direction::Up];
+        for tick in 0..3000 {
+            if tick % 90 == 0 {
+                world.apply(Command::Move(directions[tick / 90 % directions.len()]));
+            }
+            if tick % 25 == 0 {
+                world.apply(Command::Attack);
+            }
+            world.update(1.0 / 60.0);
+        }
+        serde_json::to_string(&world).unwrap()
+    }
+
+    #[test]
+    fn same_seed_same_simulation() {
+        assert_eq!(simulate(1234), simulate(1234));
+        assert_ne!(simulate(1234), simulate(4321));
+    }
+
+    // Un coup d'épée complet avec un ennemi devant le héros et un autre derrière ;
+    // renvoie les PV perdus par chacun
+    fn swing(dt: f32) -> (i32, i32, i32) {
+        let mut world = World::new(Map::new(800.0, 600.0), 1);
+        world.enemies.clear();
+        world.items.clear();
+        world.player.direction = Direction::Right;
+
+        let position = world.player.position;
+        let offset = PLAYER_RADIUS + 20.0;
+        world.enemies.push(Enemy::new(1001, position.x + offset, position.y, EnemyType::Orc));
+        world.enemies.push(Enemy::new(1002, position.x - offset, position.y, EnemyType::Orc));
+        world.reindex_enemies();
+        world.reindex_items();
+        let full_health = world.enemies[0].health;
+
+        world.apply(Command::Attack);
+        while world.player.is_attacking {
+            world.update(dt);
+        }
+
+        // Un ennemi retiré de la liste a perdu tous ses PV
+        let lost = |id: u32| world.enemies.iter().find(|e| e.id == id).map_or(full_health, |e| full_health - e.health);
+        (lost(1001), lost(1002), world.player.get_attack_damage())
+    }
+
+    #[test]
+    fn swing_hits_the_front_target_once_whatever_the_frame_rate() {
+        for dt in [1.0 / 30.0, 1.0 / 240.0] {
+            let (front, rear, damage) = swing(dt);
+            assert_eq!(front, damage, "dt = {}", dt);
+            assert_eq!(rear, 0, "dt = {}", dt);
+        }
+    }
+}